@@ -1,11 +1,14 @@
-// This is an example on how a contract deployment could look like.
+// This is an example on how a contract proposal could look like.
 // Use https://polkadot.js.org/apps/#/js and copy paste the below text.
-// Note you might want to adjust Alice's address and use a non-trivial contract_id
-// to successfully deploy this loan contract.
+// Note you might want to adjust Alice's address to successfully propose this loan contract.
+// The contract_id is ignored, the ID is derived from the terms and returned in the
+// `contracts.ContractProposed` event. The creator_id must be Alice's public key and
+// the contract is only deployed once the counterparty calls `dispatchAccept` (or anyone submits
+// the counterparty's signature over the terms with `dispatchAcceptSigned`).
 const ALICE = '5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY'
 // Create a extrinsic, transferring randomAmount units to Bob.
 const transfer = api.tx.contracts
-  .dispatchDeploy({
+  .dispatchPropose({
     // here only add the values you want, the rest will be default, i.e.
     // all options to none, all vecs to 0 length
     contract_deal_date: {
@@ -16,7 +19,6 @@ const transfer = api.tx.contracts
       minute: 0,
       second: 0
     },
-    contract_id: "0x0000000000000000000000000000000000000000000000000000000000000001",
     contract_role: 0, // ContractRole::RPA
     contract_type: 0, // ContractType::PAM
     counterparty_id: "0x0000000000000000000000000000000000000000000000000000000000000002",
@@ -41,6 +43,14 @@ const transfer = api.tx.contracts
     },
     nominal_interest_rate: "0x00000000000003e8",
     notional_principal: "0x0000000002faf080"
+  }, {
+    // The proposal expires if it isn't accepted before this time.
+    year: 2019,
+    month: 12,
+    day: 31,
+    hour: 0,
+    minute: 0,
+    second: 0
   });
 
 // Sign and Send the transaction
@@ -49,9 +59,9 @@ transfer.signAndSend(ALICE, ({
   status
 }) => {
   if (status.isFinalized) {
-    console.log('Successful deployment of ' + ' with hash ' + status.asFinalized.toHex());
+    console.log('Successful proposal of ' + ' with hash ' + status.asFinalized.toHex());
   } else {
-    console.log('Status of deployment: ' + status.type);
+    console.log('Status of proposal: ' + status.type);
   }
 
   events.forEach(({
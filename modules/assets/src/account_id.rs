@@ -0,0 +1,101 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+// This function links the accounts of the runtime to the addresses that hold the balances.
+impl<T: Trait> Module<T> {
+    /// Converts an account of the runtime into its H256 address. The SCALE encoding of the account
+    /// is used, padded with zeros (or truncated) to 32 bytes. In the runtime the accounts are
+    /// 32-byte public keys, so the address of an account is its public key.
+    pub fn account_id(who: &T::AccountId) -> H256 {
+        let encoded = who.encode();
+        let len = core::cmp::min(encoded.len(), 32);
+        let mut bytes = [0u8; 32];
+        bytes[..len].copy_from_slice(&encoded[..len]);
+        H256::from(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    // This next line should have the name of the module, in this
+    // case it is Assets
+    type Assets = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn account_id_works() {
+        new_test_ext().execute_with(|| {
+            // The accounts are u64 in the tests, they are padded with zeros.
+            let mut bytes = [0u8; 32];
+            bytes[0] = 1;
+            assert_eq!(Assets::account_id(&1), H256::from(bytes));
+            assert_ne!(Assets::account_id(&1), Assets::account_id(&2));
+        });
+    }
+}
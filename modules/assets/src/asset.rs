@@ -0,0 +1,70 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+// This struct contains the information of a registered asset.
+#[derive(Clone, Decode, Debug, Encode, Default, PartialEq)]
+pub struct Asset {
+    // The address that can mint and burn the asset. None means the asset has a fixed supply.
+    pub issuer: Option<H256>,
+    // The maximum total supply of the asset. None means there is no cap.
+    pub supply_cap: Real,
+    pub metadata: AssetMetadata,
+}
+
+// This struct contains the information needed to display an asset.
+#[derive(Clone, Decode, Debug, Encode, Default, PartialEq)]
+pub struct AssetMetadata {
+    // The ticker of the asset (e.g. "USD"), in ASCII.
+    pub symbol: Vec<u8>,
+    // The full name of the asset (e.g. "US Dollar"), in UTF-8.
+    pub name: Vec<u8>,
+    // The number of decimals that amounts of the asset can have, at most 9 (the precision of Real).
+    pub decimals: u8,
+    // The ISO 4217 code of the currency represented by the asset, if any.
+    pub iso_code: Option<[u8; 3]>,
+}
+
+// These limits keep the metadata stored for each asset small.
+pub const MAX_SYMBOL_LENGTH: usize = 12;
+pub const MAX_NAME_LENGTH: usize = 64;
+pub const MAX_DECIMALS: u8 = 9;
+
+impl AssetMetadata {
+    /// Checks that the metadata is well-formed.
+    pub fn is_valid(&self) -> bool {
+        if self.symbol.is_empty()
+            || self.symbol.len() > MAX_SYMBOL_LENGTH
+            || !self.symbol.iter().all(|c| c.is_ascii_graphic())
+        {
+            return false;
+        }
+        if self.name.len() > MAX_NAME_LENGTH || self.decimals > MAX_DECIMALS {
+            return false;
+        }
+        if let Some(code) = self.iso_code {
+            if !code.iter().all(|c| c.is_ascii_uppercase()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+// This function tells other modules (e.g. contracts) if an asset is registered.
+impl<T: Trait> Module<T> {
+    pub fn asset_exists(asset_id: u32) -> bool {
+        <Self as Store>::Assets::exists(asset_id)
+    }
+}
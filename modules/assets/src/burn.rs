@@ -21,6 +21,9 @@ impl<T: Trait> Module<T> {
             return Err("Amount can't be negative.");
         }
 
+        // Checking that amount doesn't use more decimals than the asset allows.
+        Self::check_precision(asset_id, amount)?;
+
         // Checking that from_address and asset_id exists.
         if !<Self as Store>::Balances::exists((asset_id, from_address)) {
             return Err("From_address doesn't exist at given Asset_ID.");
@@ -43,6 +46,8 @@ impl<T: Trait> Module<T> {
             <Self as Store>::Balances::insert((asset_id, from_address), new_balance);
         }
 
+        Self::deposit_event(Event::Burned(asset_id, from_address, amount));
+
         // Return Ok.
         Ok(())
     }
@@ -94,9 +99,7 @@ mod tests {
         type Version = ();
     }
     impl Trait for Test {
-        // If Events are ever added to this module, then the next line
-        // needs to be commented out.
-        // type Event = ();
+        type Event = ();
     }
     // This next line should have the name of the module, in this
     // case it is Assets
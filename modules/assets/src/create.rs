@@ -0,0 +1,178 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+// This function registers a new asset, its metadata and its issuer, the only account (besides
+// root) that can mint and burn it.
+impl<T: Trait> Module<T> {
+    pub fn create(
+        issuer: H256,
+        asset_id: u32,
+        supply_cap: Real,
+        metadata: AssetMetadata,
+    ) -> Result {
+        // Checking that the asset doesn't exist yet.
+        if <Self as Store>::Assets::exists(asset_id)
+            || <Self as Store>::TotalSupply::exists(asset_id)
+        {
+            return Err("Asset_ID already exists.");
+        }
+
+        // Checking the supply cap and the metadata.
+        Self::check_asset(supply_cap, &metadata)?;
+
+        // Storing the asset.
+        let asset = Asset {
+            issuer: Some(issuer),
+            supply_cap: supply_cap,
+            metadata: metadata,
+        };
+        <Self as Store>::Assets::insert(asset_id, asset);
+        Self::deposit_event(Event::AssetCreated(asset_id, issuer));
+
+        // Return Ok.
+        Ok(())
+    }
+
+    /// Checks that the supply cap of an asset is non-negative (None means no cap), that its
+    /// metadata is well-formed and that the supply cap doesn't use more decimals than the asset
+    /// allows.
+    pub fn check_asset(supply_cap: Real, metadata: &AssetMetadata) -> Result {
+        if supply_cap < Real::from(0) {
+            return Err("Supply cap can't be negative.");
+        }
+        if !metadata.is_valid() {
+            return Err("Asset metadata is invalid.");
+        }
+        if supply_cap.0.unwrap_or(0) % precision_unit(metadata.decimals) != 0 {
+            return Err("Supply cap has more decimals than the asset allows.");
+        }
+
+        // Return Ok.
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    // This next line should have the name of the module, in this
+    // case it is Assets
+    type Assets = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn create_works() {
+        new_test_ext().execute_with(|| {
+            // Initialize some values.
+            let issuer = H256::random();
+            let asset_id = 1;
+            let metadata = AssetMetadata {
+                symbol: b"USD".to_vec(),
+                name: b"US Dollar".to_vec(),
+                decimals: 2,
+                iso_code: Some(*b"USD"),
+            };
+
+            // Test case of negative supply cap.
+            assert!(Assets::create(issuer, asset_id, Real::from(-1), metadata.clone()).is_err());
+
+            // Test case of invalid metadata.
+            let mut invalid = metadata.clone();
+            invalid.symbol = Vec::new();
+            assert!(Assets::create(issuer, asset_id, Real(None), invalid).is_err());
+            let mut invalid = metadata.clone();
+            invalid.decimals = 10;
+            assert!(Assets::create(issuer, asset_id, Real(None), invalid).is_err());
+            let mut invalid = metadata.clone();
+            invalid.iso_code = Some(*b"usd");
+            assert!(Assets::create(issuer, asset_id, Real(None), invalid).is_err());
+
+            // Test case of a supply cap with too many decimals.
+            let cap = Real(Some(1_000_001_000));
+            assert!(Assets::create(issuer, asset_id, cap, metadata.clone()).is_err());
+
+            // Test normal case.
+            assert_ok!(Assets::create(
+                issuer,
+                asset_id,
+                Real::from(1000),
+                metadata.clone()
+            ));
+            let asset = <Assets as Store>::Assets::get(asset_id);
+            assert_eq!(asset.issuer, Some(issuer));
+            assert_eq!(asset.supply_cap, Real::from(1000));
+            assert_eq!(asset.metadata, metadata);
+            assert!(Assets::asset_exists(asset_id));
+
+            // Test case of existing asset, created or already minted.
+            assert!(Assets::create(issuer, asset_id, Real(None), metadata.clone()).is_err());
+            assert_ok!(Assets::mint(issuer, 2, Real::from(1000)));
+            assert!(Assets::create(issuer, 2, Real(None), metadata).is_err());
+        });
+    }
+}
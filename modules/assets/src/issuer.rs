@@ -0,0 +1,178 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+// These functions manage the issuer of an asset, which can mint and burn it.
+impl<T: Trait> Module<T> {
+    /// Checks that the origin can mint and burn the given asset, it must be root or the issuer of
+    /// the asset. Returns the address of the issuer, or None if the origin is root.
+    pub fn ensure_issuer(
+        origin: T::Origin,
+        asset_id: u32,
+    ) -> core::result::Result<Option<H256>, &'static str> {
+        let origin: core::result::Result<system::RawOrigin<T::AccountId>, T::Origin> =
+            origin.into();
+        match origin {
+            Ok(system::RawOrigin::Root) => Ok(None),
+            Ok(system::RawOrigin::Signed(who)) => {
+                let who = Self::account_id(&who);
+                if <Self as Store>::Assets::get(asset_id).issuer != Some(who) {
+                    return Err("Signer is not the issuer of the asset.");
+                }
+                Ok(Some(who))
+            }
+            _ => Err("Bad origin."),
+        }
+    }
+
+    /// Transfers the issuer role of an asset to another address. If the new issuer is None, the
+    /// issuer role is renounced and the asset can't be minted anymore (i.e. it has a fixed supply).
+    pub fn set_issuer(who: H256, asset_id: u32, new_issuer: Option<H256>) -> Result {
+        // Checking that the asset exists and that the signer is its issuer.
+        if !<Self as Store>::Assets::exists(asset_id) {
+            return Err("Asset_ID doesn't exist.");
+        }
+        let mut asset = <Self as Store>::Assets::get(asset_id);
+        if asset.issuer != Some(who) {
+            return Err("Signer is not the issuer of the asset.");
+        }
+
+        // Storing the new issuer.
+        asset.issuer = new_issuer;
+        <Self as Store>::Assets::insert(asset_id, asset);
+        Self::deposit_event(Event::IssuerChanged(asset_id, new_issuer));
+
+        // Return Ok.
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    // This next line should have the name of the module, in this
+    // case it is Assets
+    type Assets = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn ensure_issuer_works() {
+        new_test_ext().execute_with(|| {
+            // Initialize some values.
+            let issuer = Assets::account_id(&1);
+            let asset_id = 1;
+            let metadata = AssetMetadata {
+                symbol: b"KTL".to_vec(),
+                ..Default::default()
+            };
+            assert_ok!(Assets::create(issuer, asset_id, Real(None), metadata));
+
+            // Test root and the issuer.
+            assert_eq!(Assets::ensure_issuer(Origin::ROOT, asset_id), Ok(None));
+            assert_eq!(
+                Assets::ensure_issuer(Origin::signed(1), asset_id),
+                Ok(Some(issuer))
+            );
+
+            // Test case of other accounts and of other assets.
+            assert!(Assets::ensure_issuer(Origin::signed(2), asset_id).is_err());
+            assert!(Assets::ensure_issuer(Origin::signed(1), 2).is_err());
+            assert!(Assets::ensure_issuer(Origin::NONE, asset_id).is_err());
+        });
+    }
+
+    #[test]
+    fn set_issuer_works() {
+        new_test_ext().execute_with(|| {
+            // Initialize some values.
+            let issuer = H256::random();
+            let new_issuer = H256::random();
+            let asset_id = 1;
+            let metadata = AssetMetadata {
+                symbol: b"KTL".to_vec(),
+                ..Default::default()
+            };
+            assert_ok!(Assets::create(issuer, asset_id, Real(None), metadata));
+
+            // Test case of a signer that isn't the issuer.
+            assert!(Assets::set_issuer(new_issuer, asset_id, Some(new_issuer)).is_err());
+
+            // Test transferring the issuer role.
+            assert_ok!(Assets::set_issuer(issuer, asset_id, Some(new_issuer)));
+            assert_eq!(
+                <Assets as Store>::Assets::get(asset_id).issuer,
+                Some(new_issuer)
+            );
+            assert!(Assets::set_issuer(issuer, asset_id, Some(issuer)).is_err());
+
+            // Test renouncing the issuer role, the asset can't be minted anymore.
+            assert_ok!(Assets::set_issuer(new_issuer, asset_id, None));
+            assert_eq!(<Assets as Store>::Assets::get(asset_id).issuer, None);
+            assert!(Assets::mint(new_issuer, asset_id, Real::from(1)).is_err());
+        });
+    }
+}
@@ -13,6 +13,21 @@
 
 //! # Assets module
 //!
+//! ## Overview
+//! The Assets module keeps the balances of every asset, identified by a u32. Balances are held by
+//! H256 addresses, the address of an account of the runtime is given by `account_id` (in the
+//! runtime it is the public key of the account). Only the owner of an address can transfer its
+//! balance.
+//!
+//! Assets are registered with an issuer, which (together with root) is the only one that can mint
+//! and burn them. The issuer role can be transferred or renounced, in which case the asset has a
+//! fixed supply. Assets can optionally have a supply cap.
+//!
+//! Assets that were minted before the registry existed are registered by root, with an issuer (or
+//! a fixed supply) and metadata.
+//!
+//! Each asset has metadata (symbol, name, decimals and optionally an ISO 4217 code). Amounts of an
+//! asset can't use more decimals than the asset allows.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 // The above line is needed to compile the Wasm binaries.
@@ -20,25 +35,41 @@
 // Importing crates declared in the cargo.toml file.
 use codec::{Decode, Encode};
 use primitives::H256;
-use structures::Real;
-use support::{decl_module, decl_storage, dispatch::Result, StorageMap};
+use structures::{Real, Vec};
+use support::{decl_event, decl_module, decl_storage, dispatch::Result, StorageMap};
+use system::{ensure_root, ensure_signed};
 
 // Importing the rest of the files in this crate.
+mod account_id;
+mod asset;
 mod burn;
+mod create;
+mod issuer;
 mod mint;
+mod precision;
+mod register;
 mod transfer;
+use account_id::*;
+pub use asset::*;
 use burn::*;
+use create::*;
+use issuer::*;
 use mint::*;
+use precision::*;
+use register::*;
 use transfer::*;
 
 // This module's configuration trait.
-pub trait Trait: system::Trait {}
+pub trait Trait: system::Trait {
+    type Event: From<Event> + Into<<Self as system::Trait>::Event>;
+}
 
 // This module's storage items.
 decl_storage! {
     trait Store for Module<T: Trait> as AssetsStorage {
         pub Balances get(fn balances): map (u32, H256) => Real;
         pub TotalSupply get(fn total_supply): map u32 => Real;
+        pub Assets get(fn assets): map u32 => Asset;
     }
 }
 
@@ -47,9 +78,35 @@ decl_module! {
     // The module declaration.
     pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 
-        pub fn dispatch_transfer(origin,from_address: H256, to_address: H256, asset_id: u32, amount: Real) -> Result {
+        // Default implementation of the function that deposits the events.
+        fn deposit_event() = default;
+
+        pub fn dispatch_transfer(origin, to_address: H256, asset_id: u32, amount: Real) -> Result {
+            // Call corresponding internal function.
+            // Note: The signer can only transfer from its own address.
+            let who = ensure_signed(origin)?;
+            Self::transfer(Self::account_id(&who), to_address, asset_id, amount)?;
+
+            // Return Ok if successful.
+            Ok(())
+        }
+
+        pub fn dispatch_create(origin, asset_id: u32, supply_cap: Real, metadata: AssetMetadata) -> Result {
+            // Call corresponding internal function.
+            // Note: The signer becomes the issuer of the asset.
+            let who = ensure_signed(origin)?;
+            Self::create(Self::account_id(&who), asset_id, supply_cap, metadata)?;
+
+            // Return Ok if successful.
+            Ok(())
+        }
+
+        pub fn dispatch_register(origin, asset_id: u32, issuer: Option<H256>, supply_cap: Real, metadata: AssetMetadata) -> Result {
+            // Only chain root should be able to register the assets that are already circulating.
+            ensure_root(origin)?;
+
             // Call corresponding internal function.
-            Self::transfer(from_address, to_address, asset_id, amount)?;
+            Self::register(asset_id, issuer, supply_cap, metadata)?;
 
             // Return Ok if successful.
             Ok(())
@@ -57,18 +114,64 @@ decl_module! {
 
         pub fn dispatch_mint(origin, to_address: H256, asset_id: u32, amount: Real) -> Result {
             // Call corresponding internal function.
+            // Note: Only root and the issuer of the asset can mint it.
+            Self::ensure_issuer(origin, asset_id)?;
             Self::mint(to_address, asset_id, amount)?;
 
             // Return Ok if successful.
             Ok(())
         }
 
-        pub fn dispatch_burn(origin,from_address: H256, asset_id: u32, amount: Real) -> Result {
+        pub fn dispatch_burn(origin, from_address: H256, asset_id: u32, amount: Real) -> Result {
             // Call corresponding internal function.
+            // Note: Only root and the issuer of the asset can burn it, the issuer only from its own
+            // address.
+            if let Some(issuer) = Self::ensure_issuer(origin, asset_id)? {
+                if issuer != from_address {
+                    return Err("Issuer can only burn from its own address.");
+                }
+            }
             Self::burn(from_address, asset_id, amount)?;
 
             // Return Ok if successful.
             Ok(())
         }
+
+        pub fn dispatch_transfer_issuer(origin, asset_id: u32, new_issuer: H256) -> Result {
+            // Call corresponding internal function.
+            let who = ensure_signed(origin)?;
+            Self::set_issuer(Self::account_id(&who), asset_id, Some(new_issuer))?;
+
+            // Return Ok if successful.
+            Ok(())
+        }
+
+        pub fn dispatch_renounce_issuer(origin, asset_id: u32) -> Result {
+            // Call corresponding internal function.
+            // Note: The asset can't be minted anymore after this.
+            let who = ensure_signed(origin)?;
+            Self::set_issuer(Self::account_id(&who), asset_id, None)?;
+
+            // Return Ok if successful.
+            Ok(())
+        }
     }
 }
+
+// This module's events.
+decl_event!(
+    pub enum Event {
+        /// An asset was created by the given issuer.
+        AssetCreated(u32, H256),
+        /// An asset that was already circulating was registered by root, with the given issuer.
+        AssetRegistered(u32, Option<H256>),
+        /// The issuer of an asset changed. None means the issuer role was renounced.
+        IssuerChanged(u32, Option<H256>),
+        /// An amount of an asset was minted to an address.
+        Minted(u32, H256, Real),
+        /// An amount of an asset was burned from an address.
+        Burned(u32, H256, Real),
+        /// An amount of an asset was transferred from an address to another.
+        Transferred(u32, H256, H256, Real),
+    }
+);
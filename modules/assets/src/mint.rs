@@ -21,6 +21,24 @@ impl<T: Trait> Module<T> {
             return Err("Amount can't be negative.");
         }
 
+        // Checking that amount doesn't use more decimals than the asset allows.
+        Self::check_precision(asset_id, amount)?;
+
+        // Checking that a registered asset has an issuer and that its supply cap isn't exceeded.
+        if <Self as Store>::Assets::exists(asset_id) {
+            let asset = <Self as Store>::Assets::get(asset_id);
+            if asset.issuer == None {
+                return Err("Asset has a fixed supply.");
+            }
+            let mut supply = Real::from(0);
+            if <Self as Store>::TotalSupply::exists(asset_id) {
+                supply = <Self as Store>::TotalSupply::get(asset_id);
+            }
+            if asset.supply_cap != Real(None) && supply + amount > asset.supply_cap {
+                return Err("Amount exceeds the supply cap.");
+            }
+        }
+
         // Increasing supply.
         if <Self as Store>::TotalSupply::exists(asset_id) {
             let new_supply = <Self as Store>::TotalSupply::get(asset_id) + amount;
@@ -37,6 +55,8 @@ impl<T: Trait> Module<T> {
             <Self as Store>::Balances::insert((asset_id, to_address), amount);
         }
 
+        Self::deposit_event(Event::Minted(asset_id, to_address, amount));
+
         // Return Ok.
         Ok(())
     }
@@ -88,9 +108,7 @@ mod tests {
         type Version = ();
     }
     impl Trait for Test {
-        // If Events are ever added to this module, then the next line
-        // needs to be commented out.
-        // type Event = ();
+        type Event = ();
     }
     // This next line should have the name of the module, in this
     // case it is Assets
@@ -133,6 +151,21 @@ mod tests {
                 to_balance + amount,
                 <Assets as Store>::Balances::get((asset_id, to_address))
             );
+
+            // Test case of a registered asset with a supply cap.
+            let asset_id = 2;
+            let metadata = AssetMetadata {
+                symbol: b"KTL".to_vec(),
+                ..Default::default()
+            };
+            assert!(Assets::create(to_address, asset_id, Real::from(1000), metadata).is_ok());
+            assert!(Assets::mint(to_address, asset_id, Real::from(600)).is_ok());
+            assert!(Assets::mint(to_address, asset_id, Real::from(600)).is_err());
+            assert!(Assets::mint(to_address, asset_id, Real::from(400)).is_ok());
+            assert_eq!(
+                Real::from(1000),
+                <Assets as Store>::TotalSupply::get(asset_id)
+            );
         });
     }
 }
@@ -0,0 +1,164 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+// These functions deal with the number of decimals that amounts of an asset can have. Amounts are
+// Reals (with 9 decimals), registered assets can allow less decimals.
+impl<T: Trait> Module<T> {
+    /// Checks that an amount doesn't use more decimals than the asset allows. Unregistered assets
+    /// allow all the decimals of Real.
+    pub fn check_precision(asset_id: u32, amount: Real) -> Result {
+        if amount.0.unwrap_or(0) % Self::asset_precision_unit(asset_id) != 0 {
+            return Err("Amount has more decimals than the asset allows.");
+        }
+
+        // Return Ok.
+        Ok(())
+    }
+
+    /// Rounds an amount (half away from zero) to the decimals that the asset allows.
+    pub fn round_to_precision(asset_id: u32, amount: Real) -> Real {
+        if amount.0 == None {
+            return amount;
+        }
+        let unit = Self::asset_precision_unit(asset_id);
+        let value = amount.0.unwrap();
+        let remainder = value % unit;
+        let mut rounded = value - remainder;
+        if remainder.abs() * 2 >= unit {
+            rounded += unit * remainder.signum();
+        }
+        Real(Some(rounded))
+    }
+
+    // Returns the smallest amount of the asset, in units of the underlying i64 of Real.
+    fn asset_precision_unit(asset_id: u32) -> i64 {
+        if !<Self as Store>::Assets::exists(asset_id) {
+            return 1;
+        }
+        precision_unit(<Self as Store>::Assets::get(asset_id).metadata.decimals)
+    }
+}
+
+/// Returns the smallest amount with the given number of decimals, in units of the underlying i64
+/// of Real (which has 9 decimals).
+pub fn precision_unit(decimals: u8) -> i64 {
+    10i64.pow((MAX_DECIMALS - decimals.min(MAX_DECIMALS)) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    // This next line should have the name of the module, in this
+    // case it is Assets
+    type Assets = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn precision_works() {
+        new_test_ext().execute_with(|| {
+            // Initialize some values.
+            let asset_id = 1;
+            let metadata = AssetMetadata {
+                symbol: b"USD".to_vec(),
+                decimals: 2,
+                ..Default::default()
+            };
+            assert_ok!(Assets::create(
+                H256::random(),
+                asset_id,
+                Real(None),
+                metadata
+            ));
+
+            // Test precision_unit.
+            assert_eq!(precision_unit(9), 1);
+            assert_eq!(precision_unit(2), 10_000_000);
+            assert_eq!(precision_unit(0), 1_000_000_000);
+
+            // Test check_precision.
+            assert_ok!(Assets::check_precision(asset_id, Real(Some(1_230_000_000))));
+            assert!(Assets::check_precision(asset_id, Real(Some(1_234_000_000))).is_err());
+            assert_ok!(Assets::check_precision(2, Real(Some(1_234_567_891))));
+
+            // Test round_to_precision.
+            assert_eq!(
+                Assets::round_to_precision(asset_id, Real(Some(1_234_000_000))),
+                Real(Some(1_230_000_000))
+            );
+            assert_eq!(
+                Assets::round_to_precision(asset_id, Real(Some(1_235_000_000))),
+                Real(Some(1_240_000_000))
+            );
+            assert_eq!(
+                Assets::round_to_precision(asset_id, Real(Some(-1_235_000_000))),
+                Real(Some(-1_240_000_000))
+            );
+            assert_eq!(Assets::round_to_precision(asset_id, Real(None)), Real(None));
+        });
+    }
+}
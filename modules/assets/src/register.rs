@@ -0,0 +1,174 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+// This function registers an asset that was already circulating before the registry existed
+// (i.e. it has a total supply but no entry in the registry), so that it gets an issuer and
+// metadata. Only root can register such assets.
+impl<T: Trait> Module<T> {
+    pub fn register(
+        asset_id: u32,
+        issuer: Option<H256>,
+        supply_cap: Real,
+        metadata: AssetMetadata,
+    ) -> Result {
+        // Checking that the asset is circulating and not registered yet.
+        if <Self as Store>::Assets::exists(asset_id) {
+            return Err("Asset_ID already exists.");
+        }
+        if !<Self as Store>::TotalSupply::exists(asset_id) {
+            return Err("Asset_ID doesn't exist.");
+        }
+
+        // Checking the supply cap and the metadata.
+        Self::check_asset(supply_cap, &metadata)?;
+
+        // Checking that the current supply fits the asset.
+        // Note: The balances can't be checked one by one, amounts with more decimals than the
+        // asset allows are still transferable from the addresses holding them.
+        let supply = <Self as Store>::TotalSupply::get(asset_id);
+        if supply_cap != Real(None) && supply > supply_cap {
+            return Err("Supply exceeds the supply cap.");
+        }
+        if supply.0.unwrap_or(0) % precision_unit(metadata.decimals) != 0 {
+            return Err("Supply has more decimals than the asset allows.");
+        }
+
+        // Storing the asset.
+        let asset = Asset {
+            issuer: issuer,
+            supply_cap: supply_cap,
+            metadata: metadata,
+        };
+        <Self as Store>::Assets::insert(asset_id, asset);
+        Self::deposit_event(Event::AssetRegistered(asset_id, issuer));
+
+        // Return Ok.
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    // This next line should have the name of the module, in this
+    // case it is Assets
+    type Assets = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn register_works() {
+        new_test_ext().execute_with(|| {
+            // Initialize some values.
+            let issuer = H256::random();
+            let asset_id = 1;
+            let metadata = AssetMetadata {
+                symbol: b"USD".to_vec(),
+                name: b"US Dollar".to_vec(),
+                decimals: 2,
+                iso_code: Some(*b"USD"),
+            };
+
+            // Test case of an asset that isn't circulating.
+            assert!(
+                Assets::register(asset_id, Some(issuer), Real(None), metadata.clone()).is_err()
+            );
+
+            // Test case of a supply that exceeds the cap or has too many decimals.
+            assert_ok!(Assets::mint(issuer, asset_id, Real::from(1000)));
+            let cap = Real::from(500);
+            assert!(Assets::register(asset_id, Some(issuer), cap, metadata.clone()).is_err());
+            let mut invalid = metadata.clone();
+            invalid.decimals = 0;
+            assert_ok!(Assets::mint(issuer, asset_id, Real(Some(500_000_000))));
+            assert!(Assets::register(asset_id, Some(issuer), Real(None), invalid).is_err());
+
+            // Test case of invalid metadata.
+            let mut invalid = metadata.clone();
+            invalid.symbol = Vec::new();
+            assert!(Assets::register(asset_id, Some(issuer), Real(None), invalid).is_err());
+
+            // Test normal case.
+            assert_ok!(Assets::register(
+                asset_id,
+                Some(issuer),
+                Real::from(2000),
+                metadata.clone()
+            ));
+            let asset = <Assets as Store>::Assets::get(asset_id);
+            assert_eq!(asset.issuer, Some(issuer));
+            assert_eq!(asset.supply_cap, Real::from(2000));
+            assert_eq!(asset.metadata, metadata);
+            assert!(Assets::asset_exists(asset_id));
+
+            // Test case of an asset that is already registered.
+            assert!(Assets::register(asset_id, None, Real(None), metadata.clone()).is_err());
+            assert!(Assets::create(issuer, 2, Real(None), metadata.clone()).is_ok());
+            assert!(Assets::register(2, None, Real(None), metadata).is_err());
+        });
+    }
+}
@@ -21,6 +21,9 @@ impl<T: Trait> Module<T> {
             return Err("Amount can't be negative.");
         }
 
+        // Checking that amount doesn't use more decimals than the asset allows.
+        Self::check_precision(asset_id, amount)?;
+
         // Checking that from_address and to_address are different.
         if from_address == to_address {
             return Err("From_address and to_address can't be equal.");
@@ -52,6 +55,13 @@ impl<T: Trait> Module<T> {
             <Self as Store>::Balances::insert((asset_id, to_address), amount);
         }
 
+        Self::deposit_event(Event::Transferred(
+            asset_id,
+            from_address,
+            to_address,
+            amount,
+        ));
+
         // Return Ok.
         Ok(())
     }
@@ -103,9 +113,7 @@ mod tests {
         type Version = ();
     }
     impl Trait for Test {
-        // If Events are ever added to this module, then the next line
-        // needs to be commented out.
-        // type Event = ();
+        type Event = ();
     }
     // This next line should have the name of the module, in this
     // case it is Assets
@@ -152,6 +160,16 @@ mod tests {
             // Test case of non-existent asset_id.
             assert!(Assets::transfer(from_address, to_address, 999, amount).is_err());
 
+            // Test case of an amount with more decimals than the asset allows.
+            let metadata = AssetMetadata {
+                symbol: b"USD".to_vec(),
+                decimals: 2,
+                ..Default::default()
+            };
+            assert!(Assets::create(from_address, asset_id, Real(None), metadata).is_ok());
+            let precise_amount = Real(Some(100_001_000_000));
+            assert!(Assets::transfer(from_address, to_address, asset_id, precise_amount).is_err());
+
             // Test normal case.
             assert!(Assets::transfer(from_address, to_address, asset_id, amount).is_ok());
             assert_eq!(
@@ -0,0 +1,271 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+/// The number of seconds that an event submitted by a user can lie in the past, so that the
+/// transaction can still be included in one of the next blocks.
+pub const AUTHORIZE_TOLERANCE: u64 = 600;
+
+// This function checks that an event submitted by a user can be progressed.
+impl<T: Trait> Module<T> {
+    /// Checks if the signer of a transaction is allowed to initiate the given event. Only the
+    /// creator and the counterparty of a contract can initiate its events, only at the current
+    /// time (or shortly before, see `AUTHORIZE_TOLERANCE`) and not before the last event of the
+    /// contract, and only for the event types that aren't exclusively generated by the schedule.
+    pub fn authorize_progress(who: H256, event: ContractEvent, contract_id: H256) -> Result {
+        // Checking if the contract exists.
+        if !<Self as Store>::Contracts::exists(contract_id) {
+            return Err("Contract doesn't exist");
+        }
+        let contract = <Self as Store>::Contracts::get(contract_id);
+
+        // Checking if the signer is one of the parties of the contract.
+        if Some(who) != contract.terms.creator_id && Some(who) != contract.terms.counterparty_id {
+            return Err("Signer is not a party of the contract");
+        }
+
+        // Checking if the event happens at the current time, allowing for the delay until the
+        // transaction is included in a block.
+        let now = <timestamp::Module<T>>::get().saturated_into::<u64>();
+        if event.time > Time::from_unix(now)
+            || event.time < Time::from_unix(now.saturating_sub(AUTHORIZE_TOLERANCE))
+        {
+            return Err("Event time must be the current time or shortly before");
+        }
+        // Checking if the event happens after the last event of the contract.
+        if event.time < contract.states.status_date {
+            return Err("Event time can't be before the status date of the contract");
+        }
+
+        // Checking if the event type can be initiated by a user.
+        match event.event_type {
+            EventType::PP | EventType::TD | EventType::CE => Ok(()),
+            // Options are exercised early by their holder, at the dates the option allows.
+            EventType::XD if contract.terms.contract_type == Some(ContractType::OPTNS) => {
+                let holder = match contract.terms.contract_role {
+                    Some(ContractRole::RPA) => contract.terms.creator_id,
+                    Some(ContractRole::RPL) => contract.terms.counterparty_id,
+                    _ => None,
+                };
+                if Some(who) != holder {
+                    return Err("Only the holder of the option can exercise it");
+                }
+                Self::optns_exercisable(event.time, &contract)
+            }
+            // Contracts without a fixed schedule are drawn down, repaid and called by the parties.
+            EventType::PR | EventType::PI
+                if contract.terms.contract_type == Some(ContractType::CLM)
+                    || contract.terms.contract_type == Some(ContractType::UMP) =>
+            {
+                Ok(())
+            }
+            EventType::MD if contract.terms.contract_type == Some(ContractType::CLM) => Ok(()),
+            _ => Err("Event can only be initiated by the scheduler"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn authorize_progress_works() {
+        new_test_ext().execute_with(|| {
+            // Setting the current time.
+            <timestamp::Module<Test>>::set_timestamp(1_000_000);
+            let now = Time::from_unix(1_000_000);
+
+            // Storing a contract.
+            let id = H256::random();
+            let creator = <assets::Module<Test>>::account_id(&1);
+            let counterparty = <assets::Module<Test>>::account_id(&2);
+            let mut contract = Contract::default();
+            contract.terms.contract_type = Some(ContractType::PAM);
+            contract.terms.creator_id = Some(creator);
+            contract.terms.counterparty_id = Some(counterparty);
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+
+            // Both parties can initiate user events at the current time.
+            let event = ContractEvent::new(now, EventType::PP);
+            assert_ok!(Contracts::authorize_progress(creator, event, id));
+            assert_ok!(Contracts::authorize_progress(counterparty, event, id));
+
+            // Unknown contracts and third parties are rejected.
+            assert!(Contracts::authorize_progress(creator, event, H256::random()).is_err());
+            let third_party = <assets::Module<Test>>::account_id(&3);
+            assert!(Contracts::authorize_progress(third_party, event, id).is_err());
+
+            // Events in the past or in the future are rejected.
+            let event = ContractEvent::new(Time::from_unix(1_000_000 - 86_400), EventType::PP);
+            assert!(Contracts::authorize_progress(creator, event, id).is_err());
+            let event = ContractEvent::new(now.add_days(1), EventType::PP);
+            assert!(Contracts::authorize_progress(creator, event, id).is_err());
+
+            // Unless they were submitted shortly before the current block.
+            let earliest = Time::from_unix(1_000_000 - AUTHORIZE_TOLERANCE);
+            let event = ContractEvent::new(earliest, EventType::PP);
+            assert_ok!(Contracts::authorize_progress(creator, event, id));
+            let event = ContractEvent::new(Time::from_unix(1_000_000 - 1), EventType::PP);
+            assert_ok!(Contracts::authorize_progress(creator, event, id));
+
+            // Events before the last event of the contract are rejected.
+            let mut progressed = contract.clone();
+            progressed.states.status_date = now;
+            <Contracts as Store>::Contracts::insert(id, progressed);
+            assert!(Contracts::authorize_progress(creator, event, id).is_err());
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+
+            // Schedule-only events are rejected.
+            for event_type in [EventType::IP, EventType::RR, EventType::MD].iter() {
+                let event = ContractEvent::new(now, *event_type);
+                assert!(Contracts::authorize_progress(creator, event, id).is_err());
+            }
+
+            // Except for the contracts that are progressed by their parties.
+            contract.terms.contract_type = Some(ContractType::CLM);
+            <Contracts as Store>::Contracts::insert(id, contract);
+            let event = ContractEvent::new(now, EventType::MD);
+            assert_ok!(Contracts::authorize_progress(creator, event, id));
+            let event = ContractEvent::new(now, EventType::PR);
+            assert_ok!(Contracts::authorize_progress(counterparty, event, id));
+        });
+    }
+
+    #[test]
+    fn authorize_exercise_works() {
+        new_test_ext().execute_with(|| {
+            <timestamp::Module<Test>>::set_timestamp(1_000_000);
+            let now = Time::from_unix(1_000_000);
+            let event = ContractEvent::new(now, EventType::XD);
+
+            // Storing an american option, held by the creator.
+            let id = H256::random();
+            let holder = <assets::Module<Test>>::account_id(&1);
+            let writer = <assets::Module<Test>>::account_id(&2);
+            let mut contract = Contract::default();
+            contract.terms.contract_type = Some(ContractType::OPTNS);
+            contract.terms.contract_role = Some(ContractRole::RPA);
+            contract.terms.creator_id = Some(holder);
+            contract.terms.counterparty_id = Some(writer);
+            contract.terms.maturity_date = now.add_days(30);
+            contract.terms.option_execution_type = Some(OptionExecutionType::A);
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+
+            // Only the holder can exercise the option.
+            assert_ok!(Contracts::authorize_progress(holder, event, id));
+            assert!(Contracts::authorize_progress(writer, event, id).is_err());
+
+            // The holder is the counterparty if the creator sold the option.
+            contract.terms.contract_role = Some(ContractRole::RPL);
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+            assert!(Contracts::authorize_progress(holder, event, id).is_err());
+            assert_ok!(Contracts::authorize_progress(writer, event, id));
+            contract.terms.contract_role = Some(ContractRole::RPA);
+
+            // Bermudan options only at the dates of the optionality cycle.
+            contract.terms.option_execution_type = Some(OptionExecutionType::B);
+            contract.terms.cycle_anchor_date_of_optionality = now;
+            contract.terms.cycle_of_optionality = Some(Cycle::Days(7, true));
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+            assert_ok!(Contracts::authorize_progress(holder, event, id));
+            contract.terms.cycle_anchor_date_of_optionality = now.add_days(1);
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+            assert!(Contracts::authorize_progress(holder, event, id).is_err());
+
+            // European options only at maturity, by the schedule.
+            contract.terms.option_execution_type = Some(OptionExecutionType::E);
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+            assert!(Contracts::authorize_progress(holder, event, id).is_err());
+
+            // Futures are never exercised by the parties.
+            contract.terms.contract_type = Some(ContractType::FUTUR);
+            <Contracts as Store>::Contracts::insert(id, contract);
+            assert!(Contracts::authorize_progress(holder, event, id).is_err());
+            assert!(Contracts::authorize_progress(writer, event, id).is_err());
+        });
+    }
+}
@@ -17,7 +17,7 @@ use super::*;
 impl<T: Trait> Module<T> {
     pub fn deploy(terms: Terms) -> Result {
         // Getting the contract ID.
-        // TODO: Determine contract_id as a hash of its terms.
+        // Note: The ID is derived from the terms when the contract is proposed.
         let id = terms.contract_id;
 
         // Checking if ID is available.
@@ -25,6 +25,28 @@ impl<T: Trait> Module<T> {
             return Err("Contract ID already exists");
         }
 
+        // Checking if the contracts referenced in the contract structure exist.
+        for structure in terms.contract_structure.iter().flatten() {
+            if (structure._type == ContractReferenceType::Contract
+                || structure._type == ContractReferenceType::ContractIdentifier)
+                && !<Self as Store>::Contracts::exists(structure._object)
+            {
+                return Err("Referenced contract doesn't exist");
+            }
+        }
+
+        // Checking if the currencies of the contract are registered assets.
+        // Note: Assets that were circulating before the registry existed can be used once root
+        // registers them, see `assets::register`.
+        for currency in [terms.currency, terms.currency_2, terms.settlement_currency]
+            .iter()
+            .flatten()
+        {
+            if !<assets::Module<T>>::asset_exists(*currency) {
+                return Err("Currency isn't a registered asset");
+            }
+        }
+
         // Get current time.
         let t0 = Time::from_unix(<timestamp::Module<T>>::get().saturated_into::<u64>());
 
@@ -37,23 +59,98 @@ impl<T: Trait> Module<T> {
             Some(ContractType::ANN) => {
                 contract = Self::deploy_ann(t0, terms)?;
             }
+            Some(ContractType::LAM) => {
+                contract = Self::deploy_lam(t0, terms)?;
+            }
+            Some(ContractType::NAM) => {
+                contract = Self::deploy_nam(t0, terms)?;
+            }
+            Some(ContractType::LAX) => {
+                contract = Self::deploy_lax(t0, terms)?;
+            }
+            Some(ContractType::CLM) => {
+                contract = Self::deploy_clm(t0, terms)?;
+            }
+            Some(ContractType::UMP) => {
+                contract = Self::deploy_ump(t0, terms)?;
+            }
+            Some(ContractType::CSH) => {
+                contract = Self::deploy_csh(t0, terms)?;
+            }
+            Some(ContractType::STK) => {
+                contract = Self::deploy_stk(t0, terms)?;
+            }
+            Some(ContractType::COM) => {
+                contract = Self::deploy_com(t0, terms)?;
+            }
+            Some(ContractType::SWPPV) => {
+                contract = Self::deploy_swppv(t0, terms)?;
+            }
+            Some(ContractType::SWAPS) => {
+                contract = Self::deploy_swaps(t0, terms)?;
+            }
+            Some(ContractType::FXOUT) => {
+                contract = Self::deploy_fxout(t0, terms)?;
+            }
+            Some(ContractType::OPTNS) => {
+                contract = Self::deploy_optns(t0, terms)?;
+            }
+            Some(ContractType::FUTUR) => {
+                contract = Self::deploy_futur(t0, terms)?;
+            }
+            Some(ContractType::CAPFL) => {
+                contract = Self::deploy_capfl(t0, terms)?;
+            }
+            Some(ContractType::CEG) => {
+                contract = Self::deploy_ceg(t0, terms)?;
+            }
+            Some(ContractType::CEC) => {
+                contract = Self::deploy_cec(t0, terms)?;
+            }
             _ => {
                 contract = Err("Contract type not supported")?;
             }
         }
 
-        // Adding first event to the heap.
-        let mut heap = <Self as Store>::Scheduler::get();
-        let event = ScheduledEvent {
-            time: contract.schedule[0].time,
-            contract_id: id,
-            index: 0,
-        };
-        heap.push(event);
-        <Self as Store>::Scheduler::put(heap);
+        // Adding first event to the Scheduler.
+        // Note: Some contracts (e.g. cash positions) have no events at all.
+        if !contract.schedule.is_empty() {
+            let event = ScheduledEvent {
+                time: contract.schedule[0].time,
+                contract_id: id,
+                index: 0,
+            };
+            Self::scheduler_push(event);
+        }
+
+        // Registering the legs of the contract, they are progressed by it from now on.
+        for structure in contract.terms.contract_structure.iter().flatten() {
+            if structure._role == ContractReferenceRole::FirstLeg
+                || structure._role == ContractReferenceRole::SecondLeg
+            {
+                <Self as Store>::ParentContracts::insert(structure._object, id);
+            }
+        }
+
+        // Registering the credit enhancement with the contracts it covers, so that it is notified
+        // of their credit events.
+        for structure in contract.terms.contract_structure.iter().flatten() {
+            if structure._role == ContractReferenceRole::CoveredContract {
+                let mut enhancements = <Self as Store>::CreditEnhancements::get(structure._object);
+                if !enhancements.contains(&id) {
+                    enhancements.push(id);
+                    <Self as Store>::CreditEnhancements::insert(structure._object, enhancements);
+                }
+            }
+        }
 
         // Storing the contract state.
+        let contract_type = contract.terms.contract_type;
         <Self as Store>::Contracts::insert(id, contract);
+        Self::deposit_event(RawEvent::<T::AccountId>::ContractDeployed(
+            id,
+            contract_type,
+        ));
 
         // Return Ok if successful.
         Ok(())
@@ -116,9 +213,15 @@ mod tests {
         type OnTimestampSet = ();
         type MinimumPeriod = MinimumPeriod;
     }
-    impl oracle::Trait for Test {}
-    impl assets::Trait for Test {}
-    impl Trait for Test {}
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
     type Contracts = Module<Test>;
 
     // This function basically just builds a genesis storage key/value store according to
@@ -152,6 +255,20 @@ mod tests {
             terms.nominal_interest_rate = Real::from(1000);
             terms.notional_principal = Real(Some(50000000));
             terms.scaling_effect = None;
+
+            // The currency must be a registered asset.
+            assert!(Contracts::deploy(terms.clone()).is_err());
+            let metadata = assets::AssetMetadata {
+                symbol: b"USD".to_vec(),
+                decimals: 2,
+                ..Default::default()
+            };
+            assert_ok!(<assets::Module<Test>>::create(
+                H256::random(),
+                1,
+                Real(None),
+                metadata
+            ));
             let result = Contracts::deploy(terms.clone());
             assert!(result.is_ok());
 
@@ -159,11 +276,35 @@ mod tests {
             assert_eq!(<Contracts as Store>::Contracts::exists(id), true);
 
             // Checks if scheduler was correctly updated.
-            let event = <Contracts as Store>::Scheduler::get().pop().unwrap();
+            // Note: The first event is in the past, so it was added to the current day.
+            let day = <Contracts as Store>::SchedulerDay::get();
+            let event = Contracts::scheduler_bucket(day)[0];
             let contract = <Contracts as Store>::Contracts::get(id);
             assert_eq!(event.time, contract.schedule[0].time);
             assert_eq!(event.contract_id, contract.terms.contract_id);
             assert_eq!(event.index, 0);
+
+            // A currency that was already circulating can be used once root registers it.
+            terms.contract_id = H256::random();
+            terms.currency = Some(2);
+            assert_ok!(<assets::Module<Test>>::mint(
+                H256::random(),
+                2,
+                Real::from(1000)
+            ));
+            assert!(Contracts::deploy(terms.clone()).is_err());
+            let metadata = assets::AssetMetadata {
+                symbol: b"EUR".to_vec(),
+                decimals: 2,
+                ..Default::default()
+            };
+            assert_ok!(<assets::Module<Test>>::register(
+                2,
+                None,
+                Real(None),
+                metadata
+            ));
+            assert_ok!(Contracts::deploy(terms));
         });
     }
 }
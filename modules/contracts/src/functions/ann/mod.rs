@@ -0,0 +1,80 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+mod pof_ce_ann;
+mod pof_fp_ann;
+mod pof_ied_ann;
+mod pof_ip_ann;
+mod pof_ipcb_ann;
+mod pof_ipci_ann;
+mod pof_md_ann;
+mod pof_pp_ann;
+mod pof_pr_ann;
+mod pof_prd_ann;
+mod pof_prf_ann;
+mod pof_py_ann;
+mod pof_rr_ann;
+mod pof_rrf_ann;
+mod pof_sc_ann;
+mod pof_td_ann;
+mod stf_ce_ann;
+mod stf_fp_ann;
+mod stf_ied_ann;
+mod stf_ip_ann;
+mod stf_ipcb_ann;
+mod stf_ipci_ann;
+mod stf_md_ann;
+mod stf_pp_ann;
+mod stf_pr_ann;
+mod stf_prd_ann;
+mod stf_prf_ann;
+mod stf_py_ann;
+mod stf_rr_ann;
+mod stf_rrf_ann;
+mod stf_sc_ann;
+mod stf_td_ann;
+
+pub use pof_ce_ann::*;
+pub use pof_fp_ann::*;
+pub use pof_ied_ann::*;
+pub use pof_ip_ann::*;
+pub use pof_ipcb_ann::*;
+pub use pof_ipci_ann::*;
+pub use pof_md_ann::*;
+pub use pof_pp_ann::*;
+pub use pof_pr_ann::*;
+pub use pof_prd_ann::*;
+pub use pof_prf_ann::*;
+pub use pof_py_ann::*;
+pub use pof_rr_ann::*;
+pub use pof_rrf_ann::*;
+pub use pof_sc_ann::*;
+pub use pof_td_ann::*;
+pub use stf_ce_ann::*;
+pub use stf_fp_ann::*;
+pub use stf_ied_ann::*;
+pub use stf_ip_ann::*;
+pub use stf_ipcb_ann::*;
+pub use stf_ipci_ann::*;
+pub use stf_md_ann::*;
+pub use stf_pp_ann::*;
+pub use stf_pr_ann::*;
+pub use stf_prd_ann::*;
+pub use stf_prf_ann::*;
+pub use stf_py_ann::*;
+pub use stf_rr_ann::*;
+pub use stf_rrf_ann::*;
+pub use stf_sc_ann::*;
+pub use stf_td_ann::*;
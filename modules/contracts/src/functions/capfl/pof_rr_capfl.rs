@@ -0,0 +1,55 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    pub fn pof_rr_capfl(event: ContractEvent, contract: &Contract) -> Real {
+        let underlying = Self::underlying_contract(contract);
+
+        // The rate the underlying is reset to, before its own caps and floors are applied.
+        let rate = <oracle::Module<T>>::value_at(
+            underlying.terms.market_object_code_rate_reset.unwrap(), // This unwrap will never panic.
+            event.time,
+        ) * underlying.terms.rate_multiplier
+            + underlying.terms.rate_spread;
+
+        // The difference between the reset rate and the strikes.
+        let mut difference = Real::from(0);
+        if contract.terms.life_cap != Real(None) {
+            difference = difference + Real::max(rate - contract.terms.life_cap, Real::from(0));
+        }
+        if contract.terms.life_floor != Real(None) {
+            difference = difference + Real::max(contract.terms.life_floor - rate, Real::from(0));
+        }
+
+        // The period covered by the reset rate lasts until the next rate reset of the underlying,
+        // or until its maturity.
+        let mut t_plus = contract.states.time_at_maturity_date;
+        for e in underlying.schedule.iter() {
+            if e.event_type == EventType::RR && e.time > event.time {
+                t_plus = e.time;
+                break;
+            }
+        }
+
+        utilities::contract_role_sign(contract.terms.contract_role)
+            * difference
+            * underlying.states.notional_principal.abs()
+            * utilities::year_fraction(
+                event.time,
+                t_plus,
+                underlying.terms.day_count_convention.unwrap(), // This unwrap will never panic.
+            )
+    }
+}
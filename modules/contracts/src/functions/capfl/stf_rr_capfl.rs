@@ -0,0 +1,29 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    pub fn stf_rr_capfl(event: ContractEvent, t0: &Time, mut contract: Contract) -> Contract {
+        let underlying = Self::underlying_contract(&contract);
+        // The reset rate of the underlying is kept for reference.
+        contract.states.nominal_interest_rate = <oracle::Module<T>>::value_at(
+            underlying.terms.market_object_code_rate_reset.unwrap(), // This unwrap will never panic.
+            event.time,
+        ) * underlying.terms.rate_multiplier
+            + underlying.terms.rate_spread;
+        contract.states.status_date = event.time;
+        // Return the progressed contract state
+        contract
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+mod pof_ied_clm;
+mod pof_md_clm;
+mod pof_pi_clm;
+mod pof_pr_clm;
+mod stf_ipci_clm;
+mod stf_md_clm;
+mod stf_pi_clm;
+mod stf_pr_clm;
+mod stf_rr_clm;
+
+pub use pof_ied_clm::*;
+pub use pof_md_clm::*;
+pub use pof_pi_clm::*;
+pub use pof_pr_clm::*;
+pub use stf_ipci_clm::*;
+pub use stf_md_clm::*;
+pub use stf_pi_clm::*;
+pub use stf_pr_clm::*;
+pub use stf_rr_clm::*;
@@ -0,0 +1,23 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+pub fn pof_pr_clm(event: ContractEvent, contract: &Contract) -> Real {
+    // The amount of an unscheduled principal redemption can't exceed the outstanding notional.
+    let payoff = contract.states.notional_scaling_multiplier
+        * utilities::contract_role_sign(contract.terms.contract_role)
+        * Real::min(event.amount, contract.states.notional_principal.abs());
+    // Return the calculated payoff
+    payoff
+}
@@ -0,0 +1,32 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    pub fn stf_xd_futur(event: ContractEvent, t0: &Time, mut contract: Contract) -> Contract {
+        // The exercise amount is the change in value of the position since the last margining,
+        // or since the deal if there is no margining.
+        let value = contract.terms.quantity
+            * <oracle::Module<T>>::value_at(
+                contract.terms.market_object_code.unwrap(), // This unwrap will never panic.
+                event.time,
+            );
+        contract.states.exercise_amount = value - contract.states.notional_principal;
+        contract.states.exercise_date = event.time;
+        contract.states.notional_principal = value;
+        contract.states.status_date = event.time;
+        // Return the progressed contract state
+        contract
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+mod pof_md1_fxout;
+mod pof_md2_fxout;
+mod pof_prd_fxout;
+mod pof_std_fxout;
+mod pof_td_fxout;
+mod stf_md_fxout;
+mod stf_prd_fxout;
+mod stf_std_fxout;
+mod stf_td_fxout;
+
+pub use pof_md1_fxout::*;
+pub use pof_md2_fxout::*;
+pub use pof_prd_fxout::*;
+pub use pof_std_fxout::*;
+pub use pof_td_fxout::*;
+pub use stf_md_fxout::*;
+pub use stf_prd_fxout::*;
+pub use stf_std_fxout::*;
+pub use stf_td_fxout::*;
@@ -0,0 +1,27 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    pub fn pof_std_fxout(event: ContractEvent, contract: &Contract) -> Real {
+        // The exchange is settled in the first currency. The oracle gives the price of one unit
+        // of the second currency in units of the first currency.
+        utilities::contract_role_sign(contract.terms.contract_role)
+            * (contract.terms.notional_principal
+                - <oracle::Module<T>>::value_at(
+                    contract.terms.market_object_code.unwrap(), // This unwrap will never panic.
+                    event.time,
+                ) * contract.terms.notional_principal_2)
+    }
+}
@@ -12,3 +12,65 @@
 // GNU General Public License for more details.
 
 use super::*;
+
+mod pof_ce_lam;
+mod pof_fp_lam;
+mod pof_ied_lam;
+mod pof_ip_lam;
+mod pof_ipcb_lam;
+mod pof_ipci_lam;
+mod pof_md_lam;
+mod pof_pp_lam;
+mod pof_pr_lam;
+mod pof_prd_lam;
+mod pof_py_lam;
+mod pof_rr_lam;
+mod pof_rrf_lam;
+mod pof_sc_lam;
+mod pof_td_lam;
+mod stf_ce_lam;
+mod stf_fp_lam;
+mod stf_ied_lam;
+mod stf_ip_lam;
+mod stf_ipcb_lam;
+mod stf_ipci_lam;
+mod stf_md_lam;
+mod stf_pp_lam;
+mod stf_pr_lam;
+mod stf_prd_lam;
+mod stf_py_lam;
+mod stf_rr_lam;
+mod stf_rrf_lam;
+mod stf_sc_lam;
+mod stf_td_lam;
+
+pub use pof_ce_lam::*;
+pub use pof_fp_lam::*;
+pub use pof_ied_lam::*;
+pub use pof_ip_lam::*;
+pub use pof_ipcb_lam::*;
+pub use pof_ipci_lam::*;
+pub use pof_md_lam::*;
+pub use pof_pp_lam::*;
+pub use pof_pr_lam::*;
+pub use pof_prd_lam::*;
+pub use pof_py_lam::*;
+pub use pof_rr_lam::*;
+pub use pof_rrf_lam::*;
+pub use pof_sc_lam::*;
+pub use pof_td_lam::*;
+pub use stf_ce_lam::*;
+pub use stf_fp_lam::*;
+pub use stf_ied_lam::*;
+pub use stf_ip_lam::*;
+pub use stf_ipcb_lam::*;
+pub use stf_ipci_lam::*;
+pub use stf_md_lam::*;
+pub use stf_pp_lam::*;
+pub use stf_pr_lam::*;
+pub use stf_prd_lam::*;
+pub use stf_py_lam::*;
+pub use stf_rr_lam::*;
+pub use stf_rrf_lam::*;
+pub use stf_sc_lam::*;
+pub use stf_td_lam::*;
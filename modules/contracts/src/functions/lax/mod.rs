@@ -0,0 +1,30 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+mod pof_pi_lax;
+mod pof_pr_lax;
+mod stf_ied_lax;
+mod stf_pi_lax;
+mod stf_pr_lax;
+mod stf_rr_lax;
+mod stf_rrf_lax;
+
+pub use pof_pi_lax::*;
+pub use pof_pr_lax::*;
+pub use stf_ied_lax::*;
+pub use stf_pi_lax::*;
+pub use stf_pr_lax::*;
+pub use stf_rr_lax::*;
+pub use stf_rrf_lax::*;
@@ -0,0 +1,32 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+pub fn pof_pr_lax(event: ContractEvent, contract: &Contract) -> Real {
+    // TODO: Add O^{rf}(CURS, t)
+    // Finding the entry of the principal redemption arrays that applies to this event.
+    let i = utilities::array_index(
+        &contract
+            .terms
+            .array_cycle_anchor_date_of_principal_redemption,
+        event.time,
+    )
+    .unwrap(); // This unwrap will never panic.
+    contract.states.notional_scaling_multiplier
+        * utilities::contract_role_sign(contract.terms.contract_role)
+        * Real::min(
+            contract.terms.array_next_principal_redemption_payment[i],
+            contract.states.notional_principal.abs(),
+        )
+}
@@ -16,8 +16,32 @@
 
 use super::*;
 
+mod ann;
+mod capfl;
+mod cec;
+mod ceg;
+mod clm;
+mod futur;
+mod fxout;
 mod lam;
+mod lax;
+mod nam;
+mod optns;
 mod pam;
+mod stk;
+mod swppv;
 
+pub use ann::*;
+pub use capfl::*;
+pub use cec::*;
+pub use ceg::*;
+pub use clm::*;
+pub use futur::*;
+pub use fxout::*;
 pub use lam::*;
+pub use lax::*;
+pub use nam::*;
+pub use optns::*;
 pub use pam::*;
+pub use stk::*;
+pub use swppv::*;
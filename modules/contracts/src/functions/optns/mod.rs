@@ -0,0 +1,32 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+mod pof_prd_optns;
+mod pof_std_optns;
+mod pof_td_optns;
+mod pof_xd_optns;
+mod stf_prd_optns;
+mod stf_std_optns;
+mod stf_td_optns;
+mod stf_xd_optns;
+
+pub use pof_prd_optns::*;
+pub use pof_std_optns::*;
+pub use pof_td_optns::*;
+pub use pof_xd_optns::*;
+pub use stf_prd_optns::*;
+pub use stf_std_optns::*;
+pub use stf_td_optns::*;
+pub use stf_xd_optns::*;
@@ -0,0 +1,37 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    pub fn stf_xd_optns(event: ContractEvent, t0: &Time, mut contract: Contract) -> Contract {
+        // The price of one unit of the underlying.
+        let s = <oracle::Module<T>>::value_at(
+            contract.terms.market_object_code.unwrap(), // This unwrap will never panic.
+            event.time,
+        );
+        let amount = match contract.terms.option_type {
+            Some(OptionType::C) => Real::max(s - contract.terms.option_strike_1, Real::from(0)),
+            Some(OptionType::P) => Real::max(contract.terms.option_strike_1 - s, Real::from(0)),
+            _ => {
+                Real::max(s - contract.terms.option_strike_1, Real::from(0))
+                    + Real::max(contract.terms.option_strike_2 - s, Real::from(0))
+            }
+        };
+        contract.states.exercise_amount = contract.terms.quantity * amount;
+        contract.states.exercise_date = event.time;
+        contract.states.status_date = event.time;
+        // Return the progressed contract state
+        contract
+    }
+}
@@ -13,6 +13,6 @@
 
 use super::*;
 
-pub fn pof_ce_pam(event: Event, contract: &Contract) -> Real {
+pub fn pof_ce_pam(event: ContractEvent, contract: &Contract) -> Real {
     Real::from(0)
 }
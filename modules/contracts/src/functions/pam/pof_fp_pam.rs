@@ -13,7 +13,7 @@
 
 use super::*;
 
-pub fn pof_fp_pam(event: Event, contract: &Contract) -> Real {
+pub fn pof_fp_pam(event: ContractEvent, contract: &Contract) -> Real {
     let mut payoff = Real::from(0);
     if contract.terms.fee_basis == Some(FeeBasis::A) {
         payoff =
@@ -13,7 +13,7 @@
 
 use super::*;
 
-pub fn pof_ied_pam(event: Event, contract: &Contract) -> Real {
+pub fn pof_ied_pam(event: ContractEvent, contract: &Contract) -> Real {
     // TODO: Add O^{rf}(CURS, t)
     utilities::contract_role_sign(contract.terms.contract_role)
         * Real::from(-1)
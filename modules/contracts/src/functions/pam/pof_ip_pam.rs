@@ -13,7 +13,7 @@
 
 use super::*;
 
-pub fn pof_ip_pam(event: Event, contract: &Contract) -> Real {
+pub fn pof_ip_pam(event: ContractEvent, contract: &Contract) -> Real {
     // TODO: Add O^{rf}(CURS, t)
     contract.states.interest_scaling_multiplier
         * (contract.states.accrued_interest
@@ -13,8 +13,9 @@
 
 use super::*;
 
-pub fn pof_pp_pam(event: Event, contract: &Contract) -> Real {
+pub fn pof_pp_pam(event: ContractEvent, contract: &Contract) -> Real {
     // TODO: Add O^{rf}(CURS, t)
-    // TODO: Add the user-initiated events based on the "PP".
-    Real::from(0)
+    contract.states.notional_scaling_multiplier
+        * utilities::contract_role_sign(contract.terms.contract_role)
+        * utilities::prepayment_amount(event, contract)
 }
@@ -13,7 +13,7 @@
 
 use super::*;
 impl<T: Trait> Module<T> {
-    pub fn pof_py_pam(event: Event, contract: &Contract) -> Real {
+    pub fn pof_py_pam(event: ContractEvent, contract: &Contract) -> Real {
         // TODO: Add O^{rf}(CURS, t)
         let mut payoff = Real::from(0);
         if contract.terms.penalty_type == Some(PenaltyType::A) {
@@ -41,10 +41,10 @@ impl<T: Trait> Module<T> {
                 * Real::max(
                     Real::from(0),
                     contract.states.nominal_interest_rate
-                        - <oracle::Module<T>>::oracles(
+                        - <oracle::Module<T>>::value_at(
                             contract.terms.market_object_code_rate_reset.unwrap(), //This unwrap will never panic.
-                        )
-                        .value,
+                            event.time,
+                        ),
                 );
         }
         // Return the calculated payoff
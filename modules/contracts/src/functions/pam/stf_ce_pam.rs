@@ -13,7 +13,7 @@
 
 use super::*;
 
-pub fn stf_ce_pam(event: Event, t0: &Time, mut contract: Contract) -> Contract {
+pub fn stf_ce_pam(event: ContractEvent, t0: &Time, mut contract: Contract) -> Contract {
     contract.states.accrued_interest = contract.states.accrued_interest
         + utilities::year_fraction(
             contract.states.status_date,
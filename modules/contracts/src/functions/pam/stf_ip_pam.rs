@@ -13,7 +13,7 @@
 
 use super::*;
 
-pub fn stf_ip_pam(event: Event, t0: &Time, mut contract: Contract) -> Contract {
+pub fn stf_ip_pam(event: ContractEvent, t0: &Time, mut contract: Contract) -> Contract {
     contract.states.accrued_interest = Real::from(0);
     if contract.terms.fee_basis == Some(FeeBasis::N) {
         contract.states.fee_accrued = contract.states.fee_accrued
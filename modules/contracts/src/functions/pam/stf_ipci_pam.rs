@@ -13,7 +13,7 @@
 
 use super::*;
 
-pub fn stf_ipci_pam(event: Event, t0: &Time, mut contract: Contract) -> Contract {
+pub fn stf_ipci_pam(event: ContractEvent, t0: &Time, mut contract: Contract) -> Contract {
     let notional_principal_minus = contract.states.notional_principal; // Temporary variable.
     contract.states.notional_principal = contract.states.notional_principal
         + contract.states.accrued_interest
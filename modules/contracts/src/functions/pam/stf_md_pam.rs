@@ -13,7 +13,7 @@
 
 use super::*;
 
-pub fn stf_md_pam(event: Event, t0: &Time, mut contract: Contract) -> Contract {
+pub fn stf_md_pam(event: ContractEvent, t0: &Time, mut contract: Contract) -> Contract {
     contract.states.notional_principal = Real::from(0);
     contract.states.accrued_interest = Real::from(0);
     contract.states.fee_accrued = Real::from(0);
@@ -13,7 +13,7 @@
 
 use super::*;
 
-pub fn stf_pp_pam(event: Event, t0: &Time, mut contract: Contract) -> Contract {
+pub fn stf_pp_pam(event: ContractEvent, t0: &Time, mut contract: Contract) -> Contract {
     contract.states.accrued_interest = contract.states.accrued_interest
         + utilities::year_fraction(
             contract.states.status_date,
@@ -53,8 +53,9 @@ pub fn stf_pp_pam(event: Event, t0: &Time, mut contract: Contract) -> Contract {
             contract.terms.contract_role,
         ) * contract.terms.fee_rate;
     }
-    // TODO: Add the user-initiated events based on the "PP".
-    contract.states.notional_principal = contract.states.notional_principal;
+    contract.states.notional_principal = contract.states.notional_principal
+        - utilities::contract_role_sign(contract.terms.contract_role)
+            * utilities::prepayment_amount(event, &contract);
     contract.states.status_date = event.time;
     // Return the progressed contract state
     contract
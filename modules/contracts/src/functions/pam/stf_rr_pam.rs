@@ -14,7 +14,7 @@
 use super::*;
 
 impl<T: Trait> Module<T> {
-    pub fn stf_rr_pam(event: Event, t0: &Time, mut contract: Contract) -> Contract {
+    pub fn stf_rr_pam(event: ContractEvent, t0: &Time, mut contract: Contract) -> Contract {
         contract.states.accrued_interest = contract.states.accrued_interest
             + utilities::year_fraction(
                 contract.states.status_date,
@@ -56,11 +56,10 @@ impl<T: Trait> Module<T> {
         }
         let delta_r = Real::min(
             Real::max(
-                <oracle::Module<T>>::oracles(
+                <oracle::Module<T>>::value_at(
                     contract.terms.market_object_code_rate_reset.unwrap(), //This unwrap will never panic.
-                )
-                .value
-                    * contract.terms.rate_multiplier
+                    event.time,
+                ) * contract.terms.rate_multiplier
                     + contract.terms.rate_spread
                     - contract.states.nominal_interest_rate,
                 contract.terms.period_floor,
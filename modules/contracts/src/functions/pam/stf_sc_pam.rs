@@ -14,7 +14,7 @@
 use super::*;
 
 impl<T: Trait> Module<T> {
-    pub fn stf_sc_pam(event: Event, t0: &Time, mut contract: Contract) -> Contract {
+    pub fn stf_sc_pam(event: ContractEvent, t0: &Time, mut contract: Contract) -> Contract {
         contract.states.accrued_interest = contract.states.accrued_interest
             + utilities::year_fraction(
                 contract.states.status_date,
@@ -62,12 +62,12 @@ impl<T: Trait> Module<T> {
             contract.states.notional_scaling_multiplier =
                 contract.states.notional_scaling_multiplier;
         } else {
-            contract.states.notional_scaling_multiplier = (<oracle::Module<T>>::oracles(
-                contract.terms.market_object_code_rate_reset.unwrap(), //This unwrap will never panic.
-            )
-            .value
-                - contract.terms.scaling_index_at_status_date)
-                / contract.terms.scaling_index_at_status_date;
+            contract.states.notional_scaling_multiplier =
+                (<oracle::Module<T>>::value_at(
+                    contract.terms.market_object_code_rate_reset.unwrap(), //This unwrap will never panic.
+                    event.time,
+                ) - contract.terms.scaling_index_at_status_date)
+                    / contract.terms.scaling_index_at_status_date;
         }
         // Unwrap will never panic because of the lazy evaluation.
         if contract.terms.scaling_effect.is_some()
@@ -77,12 +77,12 @@ impl<T: Trait> Module<T> {
             contract.states.interest_scaling_multiplier =
                 contract.states.interest_scaling_multiplier;
         } else {
-            contract.states.interest_scaling_multiplier = (<oracle::Module<T>>::oracles(
-                contract.terms.market_object_code_rate_reset.unwrap(), //This unwrap will never panic.
-            )
-            .value
-                - contract.terms.scaling_index_at_status_date)
-                / contract.terms.scaling_index_at_status_date;
+            contract.states.interest_scaling_multiplier =
+                (<oracle::Module<T>>::value_at(
+                    contract.terms.market_object_code_rate_reset.unwrap(), //This unwrap will never panic.
+                    event.time,
+                ) - contract.terms.scaling_index_at_status_date)
+                    / contract.terms.scaling_index_at_status_date;
         }
         contract.states.status_date = event.time;
         // Return the progressed contract state
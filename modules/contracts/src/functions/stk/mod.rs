@@ -0,0 +1,28 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+mod pof_dv_stk;
+mod pof_prd_stk;
+mod pof_td_stk;
+mod stf_dv_stk;
+mod stf_prd_stk;
+mod stf_td_stk;
+
+pub use pof_dv_stk::*;
+pub use pof_prd_stk::*;
+pub use pof_td_stk::*;
+pub use stf_dv_stk::*;
+pub use stf_prd_stk::*;
+pub use stf_td_stk::*;
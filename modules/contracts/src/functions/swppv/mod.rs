@@ -0,0 +1,48 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+mod pof_ied_swppv;
+mod pof_ip_swppv;
+mod pof_ipfl_swppv;
+mod pof_ipfx_swppv;
+mod pof_md_swppv;
+mod pof_prd_swppv;
+mod pof_rr_swppv;
+mod pof_td_swppv;
+mod stf_ied_swppv;
+mod stf_ip_swppv;
+mod stf_ipfl_swppv;
+mod stf_ipfx_swppv;
+mod stf_md_swppv;
+mod stf_prd_swppv;
+mod stf_rr_swppv;
+mod stf_td_swppv;
+
+pub use pof_ied_swppv::*;
+pub use pof_ip_swppv::*;
+pub use pof_ipfl_swppv::*;
+pub use pof_ipfx_swppv::*;
+pub use pof_md_swppv::*;
+pub use pof_prd_swppv::*;
+pub use pof_rr_swppv::*;
+pub use pof_td_swppv::*;
+pub use stf_ied_swppv::*;
+pub use stf_ip_swppv::*;
+pub use stf_ipfl_swppv::*;
+pub use stf_ipfx_swppv::*;
+pub use stf_md_swppv::*;
+pub use stf_prd_swppv::*;
+pub use stf_rr_swppv::*;
+pub use stf_td_swppv::*;
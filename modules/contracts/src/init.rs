@@ -19,6 +19,9 @@ impl<T: Trait> Module<T> {
         // Get current time.
         let t = Time::from_unix(<timestamp::Module<T>>::get().saturated_into::<u64>());
 
+        // Moving the events of an earlier version of the Scheduler, after a runtime upgrade.
+        Self::scheduler_migrate();
+
         // Calculating the initial contract state.
         Self::scheduler_run(t)?;
 
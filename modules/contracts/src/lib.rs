@@ -16,40 +16,92 @@
 
 // Importing crates necessary to work with Substrate.
 use codec::{Decode, Encode};
-use primitives::H256;
-use support::{decl_module, decl_storage, dispatch::Result, StorageMap, StorageValue};
-// This import is used to convert the timestamp to a Time.
-use sr_primitives::traits::SaturatedConversion;
+use primitives::{crypto::AccountId32, H256};
+use support::{decl_event, decl_module, decl_storage, dispatch::Result, StorageMap, StorageValue};
+use system::{ensure_root, ensure_signed};
+// These imports are used to convert the timestamp to a Time, to verify signatures and to derive
+// the contract IDs.
+use sr_primitives::{
+    traits::{BlakeTwo256, Hash as HashT, SaturatedConversion, Verify, Zero},
+    MultiSignature,
+};
 
 // Importing types and structures.
 use structures::*;
 
 // Importing the rest of the files in this crate.
+mod authorize;
 mod deploy;
 mod functions;
 mod init;
+mod migrate;
 mod progress;
+mod propose;
 mod scheduler;
 mod storage;
 mod types;
 mod utilities;
+use authorize::*;
 use deploy::*;
 use functions::*;
 use init::*;
+use migrate::*;
 use progress::*;
+use propose::*;
 use scheduler::*;
 use storage::*;
 use types::*;
 use utilities::*;
 
 // This module's configuration trait.
-pub trait Trait: system::Trait + oracle::Trait + assets::Trait + timestamp::Trait {}
+pub trait Trait: system::Trait + oracle::Trait + assets::Trait + timestamp::Trait {
+    type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+}
 
 // This module's storage items.
 decl_storage! {
     trait Store for Module<T: Trait> as ContractsStorage {
         pub Contracts: map H256 => Contract;
-        pub Scheduler: MinHeap<ScheduledEvent> = MinHeap::new();
+        // The latest events executed by each contract, keyed by their position in its history.
+        pub ContractHistory get(fn contract_history): map (H256, u32) => ExecutedEvent;
+        // The positions of the earliest event kept and of the next event in the history of each
+        // contract.
+        pub ContractHistoryRange: map H256 => (u32, u32);
+        // The payoffs that non-performing contracts couldn't settle yet, in each currency.
+        pub Arrears get(fn arrears): map H256 => Vec<(Option<u32>, Real)>;
+        // Maps the child contracts (e.g. the legs of a swap) to the contract they belong to.
+        pub ParentContracts: map H256 => H256;
+        // Maps the covered contracts to the credit enhancements (CEG and CEC) covering them.
+        pub CreditEnhancements: map H256 => Vec<H256>;
+        // Contracts proposed by their creator and waiting to be accepted by their counterparty.
+        pub Proposals: map H256 => Proposal;
+        // The number of contracts proposed by each account, used to derive the contract IDs.
+        pub Nonces: map H256 => u64;
+        // The scheduled events, bucketed by the day (counted from the unix epoch) they are due and
+        // keyed by their slot in the bucket, so that they can be added and removed one by one.
+        pub ScheduledEvents: map (u32, u32) => ScheduledEvent;
+        // The number of slots used in the bucket of each day, slots aren't reused.
+        pub SchedulerSlots: map u32 => u32;
+        // The first slot of the bucket of each day that might hold a scheduled event, all the
+        // slots before it are empty.
+        pub SchedulerCursor: map u32 => u32;
+        // The number of scheduled events in the bucket of each day.
+        pub SchedulerPending: map u32 => u32;
+        // The day and slot of the pending scheduled event of each contract.
+        pub PendingEvents: map H256 => (u32, u32);
+        // The first day whose bucket of scheduled events hasn't been completed yet.
+        pub SchedulerDay: u32;
+        // The maximum number of events executed by the Scheduler in a block.
+        pub MaxEventsPerBlock get(fn max_events_per_block): u32 = 1000;
+        // The number of events left in the first day that hasn't been completed yet, if the last
+        // run of the Scheduler reached the limit of events per block. Later days might have due
+        // events too.
+        pub SchedulerBacklog get(fn scheduler_backlog): u32;
+        // The contracts whose events failed in the Scheduler, until they succeed again.
+        pub SchedulerFailures get(fn scheduler_failures): map H256 => SchedulerFailure;
+        // The binary heap of scheduled events of earlier versions of the Scheduler, its events are
+        // moved to the buckets by `scheduler_migrate`.
+        pub Scheduler: Option<MinHeap<ScheduledEvent>>;
     }
 }
 
@@ -58,23 +110,94 @@ decl_module! {
     // The module declaration.
     pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 
+        // Default implementation of the function that deposits the events.
+        fn deposit_event() = default;
+
         fn on_initialize(_now: T::BlockNumber) {
             // At the beginning of each block execution, system triggers all
             // `on_initialize` functions, which allows us to run the schedule.
             Self::init();
         }
 
-        pub fn dispatch_deploy(origin, terms: Terms) -> Result {
+        pub fn dispatch_propose(origin, terms: Terms, expiry: Time) -> Result {
+            // Call corresponding internal function.
+            let who = ensure_signed(origin)?;
+            let id = Self::propose(<assets::Module<T>>::account_id(&who), terms, expiry)?;
+            Self::deposit_event(RawEvent::ContractProposed(who, id));
+
+            // Return Ok if successful.
+            Ok(())
+        }
+
+        pub fn dispatch_accept(origin, contract_id: H256) -> Result {
+            // Call corresponding internal function.
+            let who = ensure_signed(origin)?;
+            Self::accept(<assets::Module<T>>::account_id(&who), contract_id)?;
+
+            // Return Ok if successful.
+            Ok(())
+        }
+
+        pub fn dispatch_accept_signed(origin, contract_id: H256, signature: MultiSignature) -> Result {
             // Call corresponding internal function.
-            // TODO: Check for third party signatures
-            Self::deploy(terms)?;
+            // Note: The acceptance is signed off-chain by the counterparty, anyone can submit it.
+            ensure_signed(origin)?;
+            Self::accept_signed(contract_id, signature)?;
 
             // Return Ok if successful.
             Ok(())
         }
 
-        pub fn dispatch_progress(origin, event: Event, contract_id: H256) -> Result {
-            // TODO: Assert rules for user initiated events
+        pub fn dispatch_withdraw(origin, contract_id: H256) -> Result {
+            // Call corresponding internal function.
+            let who = ensure_signed(origin)?;
+            Self::withdraw(<assets::Module<T>>::account_id(&who), contract_id)?;
+
+            // Return Ok if successful.
+            Ok(())
+        }
+
+        pub fn dispatch_set_max_events_per_block(origin, max_events: u32) -> Result {
+            // Only chain root should be able to set this value.
+            ensure_root(origin)?;
+
+            // Call corresponding internal function.
+            Self::set_max_events_per_block(max_events)?;
+
+            // Return Ok if successful.
+            Ok(())
+        }
+
+        pub fn dispatch_resume(origin, contract_id: H256) -> Result {
+            // Only chain root should be able to resume a halted contract.
+            ensure_root(origin)?;
+
+            // Call corresponding internal function.
+            let now = Time::from_unix(<timestamp::Module<T>>::get().saturated_into::<u64>());
+            Self::scheduler_resume(now, contract_id)?;
+
+            // Return Ok if successful.
+            Ok(())
+        }
+
+        pub fn dispatch_migrate(origin, contract_ids: Vec<H256>) -> Result {
+            // Only chain root should be able to migrate the contracts of earlier versions.
+            ensure_root(origin)?;
+
+            // Call corresponding internal function.
+            for contract_id in contract_ids {
+                Self::contract_migrate(contract_id)?;
+            }
+
+            // Return Ok if successful.
+            Ok(())
+        }
+
+        pub fn dispatch_progress(origin, event: ContractEvent, contract_id: H256) -> Result {
+            // Checking if the signer is allowed to initiate this event.
+            // Note: Scheduled events are progressed directly by the scheduler, not through here.
+            let who = ensure_signed(origin)?;
+            Self::authorize_progress(<assets::Module<T>>::account_id(&who), event, contract_id)?;
 
             // Call corresponding internal function.
             Self::progress(event, contract_id)?;
@@ -84,3 +207,27 @@ decl_module! {
         }
     }
 }
+
+// This module's events.
+decl_event!(
+    pub enum Event<T>
+    where
+        AccountId = <T as system::Trait>::AccountId,
+    {
+        /// A contract was proposed by an account, it can be accepted under the given ID.
+        ContractProposed(AccountId, H256),
+        /// A contract of the given type was deployed.
+        ContractDeployed(H256, Option<ContractType>),
+        /// An event of a contract was executed, with the payoffs (and their currencies) that were
+        /// settled.
+        ContractEventExecuted(H256, EventType, Time, Vec<(Option<u32>, Real)>),
+        /// The performance of a contract changed (e.g. it became delinquent).
+        ContractPerformanceChanged(H256, Option<ContractPerformance>),
+        /// The Scheduler reached the limit of events per block with due events left in the given
+        /// day, they are executed in the next blocks.
+        SchedulerBacklog(u32, u32),
+        /// An event of a contract failed in the Scheduler, with the number of consecutive failed
+        /// attempts and the error. The contract is halted after the last attempt.
+        SchedulerFailed(H256, u32, Vec<u8>),
+    }
+);
@@ -0,0 +1,239 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+use support::storage::unhashed;
+
+// This struct is the layout of the contracts stored by earlier versions of this module, whose
+// events had no amount.
+#[derive(Clone, Decode, Debug, Encode, PartialEq)]
+struct LegacyContract {
+    terms: Terms,
+    states: States,
+    schedule: Vec<LegacyContractEvent>,
+}
+
+#[derive(Clone, Copy, Decode, Debug, Encode, PartialEq)]
+struct LegacyContractEvent {
+    time: Time,
+    event_type: EventType,
+}
+
+// This function migrates the contracts stored by earlier versions of this module.
+impl<T: Trait> Module<T> {
+    /// Re-encodes a contract stored by an earlier version of this module in the current layout,
+    /// the events of its schedule getting no amount. Contracts that are already stored in the
+    /// current layout are left untouched. The contracts with a pending event are migrated by
+    /// `scheduler_migrate` after the runtime upgrade, the other ones have to be migrated by root.
+    pub fn contract_migrate(contract_id: H256) -> Result {
+        let key = <Self as Store>::Contracts::hashed_key_for(contract_id);
+        let bytes = match unhashed::get_raw(&key) {
+            Some(bytes) => bytes,
+            None => return Err("Contract doesn't exist"),
+        };
+
+        // Note: Decoding doesn't check that the whole input was read, so the layout of the
+        // contract is the one that reads exactly all of its bytes.
+        let mut input = &bytes[..];
+        if Contract::decode(&mut input).is_ok() && input.is_empty() {
+            return Ok(());
+        }
+        let mut input = &bytes[..];
+        let legacy = match LegacyContract::decode(&mut input) {
+            Ok(legacy) if input.is_empty() => legacy,
+            _ => return Err("Contract has an unknown layout"),
+        };
+
+        let contract = Contract {
+            terms: legacy.terms,
+            states: legacy.states,
+            schedule: legacy
+                .schedule
+                .iter()
+                .map(|event| ContractEvent::new(event.time, event.event_type))
+                .collect(),
+        };
+        <Self as Store>::Contracts::insert(contract_id, contract);
+
+        // Return Ok if successful.
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn contract_migrate_works() {
+        new_test_ext().execute_with(|| {
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_type = Some(ContractType::PAM);
+            let states = States::default();
+            let t1 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let t2 = Time::from_values(2016, 01, 01, 00, 00, 00);
+
+            // Contracts that don't exist can't be migrated.
+            assert!(Contracts::contract_migrate(id).is_err());
+
+            // A contract stored by an earlier version of this module.
+            let legacy = LegacyContract {
+                terms: terms.clone(),
+                states: states,
+                schedule: vec![
+                    LegacyContractEvent {
+                        time: t1,
+                        event_type: EventType::IED,
+                    },
+                    LegacyContractEvent {
+                        time: t2,
+                        event_type: EventType::MD,
+                    },
+                ],
+            };
+            let key = <Contracts as Store>::Contracts::hashed_key_for(id);
+            unhashed::put_raw(&key, &legacy.encode());
+
+            // It can't be decoded until it is migrated, its events then have no amount.
+            let contract = Contract {
+                terms: terms,
+                states: states,
+                schedule: vec![
+                    ContractEvent::new(t1, EventType::IED),
+                    ContractEvent::new(t2, EventType::MD),
+                ],
+            };
+            assert_ne!(<Contracts as Store>::Contracts::get(id), contract);
+            assert_ok!(Contracts::contract_migrate(id));
+            assert_eq!(<Contracts as Store>::Contracts::get(id), contract);
+
+            // Contracts in the current layout are left untouched.
+            let mut contract = contract;
+            contract.schedule[1] = ContractEvent::with_amount(t2, EventType::PP, Real::from(100));
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+            assert_ok!(Contracts::contract_migrate(id));
+            assert_eq!(<Contracts as Store>::Contracts::get(id), contract);
+        });
+    }
+
+    #[test]
+    fn scheduler_migrate_migrates_the_contracts() {
+        new_test_ext().execute_with(|| {
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_type = Some(ContractType::PAM);
+            let t = Time::from_values(2015, 01, 02, 00, 00, 00);
+            let legacy = LegacyContract {
+                terms: terms.clone(),
+                states: States::default(),
+                schedule: vec![LegacyContractEvent {
+                    time: t,
+                    event_type: EventType::IED,
+                }],
+            };
+            let key = <Contracts as Store>::Contracts::hashed_key_for(id);
+            unhashed::put_raw(&key, &legacy.encode());
+
+            // The heap of an earlier version of the Scheduler.
+            let mut heap = MinHeap::new();
+            heap.push(ScheduledEvent {
+                time: t,
+                contract_id: id,
+                index: 0,
+            });
+            <Contracts as Store>::Scheduler::put(heap);
+
+            // The contract of its event is migrated with it.
+            Contracts::scheduler_migrate();
+            let contract = Contract {
+                terms: terms,
+                states: States::default(),
+                schedule: vec![ContractEvent::new(t, EventType::IED)],
+            };
+            assert_eq!(<Contracts as Store>::Contracts::get(id), contract);
+        });
+    }
+}
@@ -13,13 +13,203 @@
 
 use super::*;
 
+/// The number of executed events kept in the history of a contract, the earlier ones are removed.
+/// All the executed events are deposited as module events as well.
+pub const CONTRACT_HISTORY_LENGTH: u32 = 1000;
+
 // This function creates a new ACTUS contract.
 impl<T: Trait> Module<T> {
-    pub fn progress(event: Event, contract_id: H256) -> Result {
+    pub fn progress(event: ContractEvent, contract_id: H256) -> Result {
+        // Child contracts are progressed by their parent contract.
+        if <Self as Store>::ParentContracts::exists(contract_id) {
+            return Err("Contract is progressed by its parent");
+        }
+
         // Getting the contract.
         let mut contract = <Self as Store>::Contracts::get(contract_id);
+        let contract_performance = contract.states.contract_performance;
+        let schedule = contract.schedule.clone();
+
+        // Updating the performance of a non-performing contract, and calculating the penalty it
+        // accrued since its last event.
+        let mut non_performing_date = contract.states.non_performing_date;
+        if non_performing_date == Time(None) {
+            non_performing_date = contract.terms.non_performing_date;
+        }
+        contract.states.contract_performance = utilities::performance_transition(
+            contract.states.contract_performance,
+            non_performing_date,
+            event.time,
+            contract.terms.grace_period,
+            contract.terms.delinquency_period,
+        );
+        let penalty = utilities::delinquency_penalty(&contract, event.time);
 
         // Calculating the resulting contract contract.
+        // Note: Some contracts have payoffs in several currencies or settled gross, which are
+        // executed separately.
+        let mut payoffs: Vec<(Option<u32>, Real)> = Vec::new();
+        let mut legs: Vec<(H256, Contract)> = Vec::new();
+        let currency = contract.terms.settlement_currency;
+        match contract.terms.contract_type {
+            Some(ContractType::SWAPS) => {
+                let result = Self::progress_swaps(event, contract)?;
+                contract = result.2;
+                legs = result.3;
+                if contract.terms.delivery_settlement == Some(DeliverySettlement::D) {
+                    payoffs.push((currency, result.0 + penalty));
+                    payoffs.push((currency, result.1));
+                } else {
+                    payoffs.push((currency, result.0 + result.1 + penalty));
+                }
+            }
+            Some(ContractType::FXOUT) => {
+                let result = Self::progress_fxout(event, contract)?;
+                contract = result.2;
+                payoffs.push((contract.terms.currency, result.0 + penalty));
+                payoffs.push((contract.terms.currency_2, result.1));
+            }
+            _ => {
+                let result = Self::progress_payoff(event, contract)?;
+                contract = result.1;
+                payoffs.push((currency, result.0 + penalty));
+            }
+        }
+
+        // Executing the payoffs, together with the arrears of the contract.
+        // Note: The payoffs of defaulted contracts are zero, and their arrears aren't settled
+        // anymore. The payoffs are settled all together or not at all (e.g. both legs of a swap).
+        // If they can't be settled, the contract becomes non-performing instead of being stuck at
+        // this event and they are recorded as arrears, until the contract can settle them.
+        let contract_default = utilities::contract_default(contract.states.contract_performance);
+        let defaulted = contract_default == Real::from(0);
+        let mut due_payoffs: Vec<(Option<u32>, Real)> = Vec::new();
+        if !defaulted {
+            due_payoffs = <Self as Store>::Arrears::get(contract_id);
+        }
+        for (currency, payoff) in payoffs {
+            let mut payoff = payoff;
+            if contract_default != Real(None) {
+                payoff = payoff * contract_default;
+            }
+            due_payoffs.push((currency, payoff));
+        }
+        // Note: A payoff that couldn't be calculated (e.g. an oracle without a value) isn't a
+        // missed payment, the event fails and nothing is stored.
+        let transfers = Self::settlement_transfers(&contract, &due_payoffs)?;
+        let mut executed_payoffs: Vec<(Option<u32>, Real)> = Vec::new();
+        if Self::execute_transfers(transfers).is_ok() {
+            executed_payoffs = due_payoffs;
+            // A non-performing contract that paid its arrears is performant again.
+            if !defaulted && <Self as Store>::Arrears::exists(contract_id) {
+                <Self as Store>::Arrears::remove(contract_id);
+                contract.states.contract_performance = Some(ContractPerformance::PF);
+                contract.states.non_performing_date = Time(None);
+            }
+        } else {
+            let mut arrears: Vec<(Option<u32>, Real)> = Vec::new();
+            if defaulted {
+                arrears = <Self as Store>::Arrears::get(contract_id);
+            }
+            for (currency, payoff) in due_payoffs {
+                if payoff == Real::from(0) || payoff == Real(None) {
+                    continue;
+                }
+                match arrears.iter_mut().find(|arrear| arrear.0 == currency) {
+                    Some(arrear) => arrear.1 = arrear.1 + payoff,
+                    None => arrears.push((currency, payoff)),
+                }
+            }
+            <Self as Store>::Arrears::insert(contract_id, arrears);
+            if contract.states.contract_performance == Some(ContractPerformance::PF)
+                || contract.states.contract_performance.is_none()
+            {
+                contract.states.contract_performance = Some(ContractPerformance::DL);
+                contract.states.non_performing_date = event.time;
+            }
+        }
+
+        // Terminated contracts don't execute their remaining events, they are removed from the
+        // schedule and from the Scheduler.
+        if scheduler::terminates(event, &contract) {
+            contract.schedule.retain(|e| e.time <= event.time);
+            Self::scheduler_cancel(contract_id);
+        } else if !schedule.contains(&event)
+            && contract.schedule != schedule
+            && Self::pending_event(contract_id).is_some()
+        {
+            // A user initiated event can change the schedule of a contract (e.g. an option
+            // exercised by its holder is settled earlier), its pending scheduled event is then
+            // replaced by the next event of the new schedule.
+            match contract.schedule.iter().position(|e| *e > event) {
+                Some(index) => Self::scheduler_push(ScheduledEvent {
+                    time: contract.schedule[index].time,
+                    contract_id: contract_id,
+                    index: index as u32,
+                }),
+                None => Self::scheduler_cancel(contract_id),
+            }
+        }
+
+        // Storing the contract contract and its progressed legs, and recording the event in its
+        // history.
+        let new_performance = contract.states.contract_performance;
+        <Self as Store>::Contracts::insert(contract_id, contract);
+        for (leg_id, leg) in legs {
+            <Self as Store>::Contracts::insert(leg_id, leg);
+        }
+        let (mut first, next) = <Self as Store>::ContractHistoryRange::get(contract_id);
+        <Self as Store>::ContractHistory::insert(
+            (contract_id, next),
+            ExecutedEvent {
+                event: event,
+                block_number: <system::Module<T>>::block_number().saturated_into::<u64>(),
+                payoffs: executed_payoffs.clone(),
+            },
+        );
+        if next + 1 - first > CONTRACT_HISTORY_LENGTH {
+            <Self as Store>::ContractHistory::remove((contract_id, first));
+            first += 1;
+        }
+        <Self as Store>::ContractHistoryRange::insert(contract_id, (first, next + 1));
+        Self::deposit_event(RawEvent::<T::AccountId>::ContractEventExecuted(
+            contract_id,
+            event.event_type,
+            event.time,
+            executed_payoffs,
+        ));
+
+        // Notifying the credit enhancements covering the contract of a change of performance.
+        if new_performance != contract_performance {
+            Self::deposit_event(RawEvent::<T::AccountId>::ContractPerformanceChanged(
+                contract_id,
+                new_performance,
+            ));
+            Self::credit_event(contract_id, event.time);
+        }
+
+        // Return Ok if successful.
+        Ok(())
+    }
+
+    /// Returns the executed events kept in the history of a contract, from the earliest to the
+    /// latest.
+    pub fn executed_events(contract_id: H256) -> Vec<ExecutedEvent> {
+        let (first, next) = <Self as Store>::ContractHistoryRange::get(contract_id);
+        (first..next)
+            .map(|index| <Self as Store>::ContractHistory::get((contract_id, index)))
+            .collect()
+    }
+
+    /// Calculates the payoff of an event and the resulting contract, without executing the
+    /// payoff.
+    pub fn progress_payoff(
+        event: ContractEvent,
+        mut contract: Contract,
+    ) -> ContractResult<(Real, Contract)> {
+        // Events that read an oracle need its value at the time of the event.
+        Self::check_oracle_values(event, &contract)?;
+
         let mut payoff = Real::from(0);
         match contract.terms.contract_type {
             Some(ContractType::PAM) => {
@@ -27,36 +217,218 @@ impl<T: Trait> Module<T> {
                 payoff = result.0;
                 contract = result.1;
             }
+            Some(ContractType::ANN) => {
+                let result = Self::progress_ann(event, contract)?;
+                payoff = result.0;
+                contract = result.1;
+            }
+            Some(ContractType::LAM) => {
+                let result = Self::progress_lam(event, contract)?;
+                payoff = result.0;
+                contract = result.1;
+            }
+            Some(ContractType::NAM) => {
+                let result = Self::progress_nam(event, contract)?;
+                payoff = result.0;
+                contract = result.1;
+            }
+            Some(ContractType::LAX) => {
+                let result = Self::progress_lax(event, contract)?;
+                payoff = result.0;
+                contract = result.1;
+            }
+            Some(ContractType::CLM) => {
+                let result = Self::progress_clm(event, contract)?;
+                payoff = result.0;
+                contract = result.1;
+            }
+            Some(ContractType::UMP) => {
+                let result = Self::progress_ump(event, contract)?;
+                payoff = result.0;
+                contract = result.1;
+            }
+            Some(ContractType::CSH) => {
+                let result = Self::progress_csh(event, contract)?;
+                payoff = result.0;
+                contract = result.1;
+            }
+            Some(ContractType::STK) => {
+                let result = Self::progress_stk(event, contract)?;
+                payoff = result.0;
+                contract = result.1;
+            }
+            Some(ContractType::COM) => {
+                let result = Self::progress_com(event, contract)?;
+                payoff = result.0;
+                contract = result.1;
+            }
+            Some(ContractType::SWPPV) => {
+                let result = Self::progress_swppv(event, contract)?;
+                payoff = result.0;
+                contract = result.1;
+            }
+            Some(ContractType::OPTNS) => {
+                let result = Self::progress_optns(event, contract)?;
+                payoff = result.0;
+                contract = result.1;
+            }
+            Some(ContractType::FUTUR) => {
+                let result = Self::progress_futur(event, contract)?;
+                payoff = result.0;
+                contract = result.1;
+            }
+            Some(ContractType::CAPFL) => {
+                let result = Self::progress_capfl(event, contract)?;
+                payoff = result.0;
+                contract = result.1;
+            }
+            Some(ContractType::CEG) => {
+                let result = Self::progress_ceg(event, contract)?;
+                payoff = result.0;
+                contract = result.1;
+            }
+            Some(ContractType::CEC) => {
+                let result = Self::progress_cec(event, contract)?;
+                payoff = result.0;
+                contract = result.1;
+            }
             _ => {
-                Err("Contract type not supported")?;
+                return Err("Contract type not supported");
             }
         }
 
-        // Executing the payoff.
-        // Note: not sure if those unwrap() will not panic.
-        // TODO: Real is Option<i64> but use generic_asset T::Balance
-        if payoff >= Real::from(0) {
-            <assets::Module<T>>::transfer(
-                contract.terms.counterparty_id.unwrap(),
-                contract.terms.creator_id.unwrap(),
-                contract.terms.settlement_currency.unwrap(),
-                payoff.abs(),
-            )?;
-        } else {
-            <assets::Module<T>>::transfer(
-                contract.terms.creator_id.unwrap(),
-                contract.terms.counterparty_id.unwrap(),
-                contract.terms.settlement_currency.unwrap(),
-                payoff.abs(),
-            )?;
+        Ok((payoff, contract))
+    }
+
+    /// Checks that the oracles read by an event have a value at the time of the event, so that
+    /// its payoff and the resulting contract can be calculated.
+    pub fn check_oracle_values(event: ContractEvent, contract: &Contract) -> Result {
+        for id in Self::oracle_objects(event, contract) {
+            if <oracle::Module<T>>::value_at(id, event.time) == Real(None) {
+                return Err("Oracle has no value at the time of the event");
+            }
         }
+        Ok(())
+    }
 
-        // TODO: Set contract performance variable to something other than `Performant`
+    /// Returns the oracles read by an event.
+    fn oracle_objects(event: ContractEvent, contract: &Contract) -> Vec<H256> {
+        let mut objects: Vec<Option<H256>> = Vec::new();
+        match (contract.terms.contract_type, event.event_type) {
+            // The rate resets of a cap or floor read the rate of its underlying.
+            (Some(ContractType::CAPFL), EventType::RR) => {
+                let underlying = Self::underlying_contract(contract);
+                objects.push(underlying.terms.market_object_code_rate_reset);
+            }
+            (_, EventType::RR) => objects.push(contract.terms.market_object_code_rate_reset),
+            (_, EventType::SC) if contract.terms.scaling_effect != Some(ScalingEffect::_000) => {
+                objects.push(contract.terms.market_object_code_rate_reset)
+            }
+            (_, EventType::PY) if contract.terms.penalty_type == Some(PenaltyType::I) => {
+                objects.push(contract.terms.market_object_code_rate_reset)
+            }
+            // An option that was already exercised isn't valued again.
+            (Some(ContractType::OPTNS), EventType::XD)
+                if contract.states.exercise_date == Time(None) =>
+            {
+                objects.push(contract.terms.market_object_code)
+            }
+            (Some(ContractType::FUTUR), EventType::MR)
+            | (Some(ContractType::FUTUR), EventType::XD)
+            | (Some(ContractType::FXOUT), EventType::STD) => {
+                objects.push(contract.terms.market_object_code)
+            }
+            // The collateral of a credit enhancement is valued at its exercise.
+            (Some(ContractType::CEC), EventType::XD) => {
+                for structure in contract.terms.contract_structure.iter().flatten() {
+                    if structure._role == ContractReferenceRole::CoveringContract {
+                        let covering = <Self as Store>::Contracts::get(structure._object);
+                        if covering.terms.contract_type == Some(ContractType::STK)
+                            || covering.terms.contract_type == Some(ContractType::COM)
+                        {
+                            objects.push(covering.terms.market_object_code);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        objects.into_iter().flatten().collect()
+    }
 
-        // Storing the contract contract.
-        <Self as Store>::Contracts::insert(contract_id, contract);
+    /// Executes payoffs, in their currency, between the creator and the counterparty of a
+    /// contract. Either all of the payoffs are executed or none of them. The payoffs are rounded to
+    /// the decimals of their currency, null payoffs don't need to be executed.
+    pub fn settle(contract: &Contract, payoffs: &[(Option<u32>, Real)]) -> Result {
+        let transfers = Self::settlement_transfers(contract, payoffs)?;
+        Self::execute_transfers(transfers)
+    }
+
+    /// Returns the transfers (currency, from, to, amount) that settle payoffs. It fails if a
+    /// payoff couldn't be calculated or if the contract has no currency or parties, but not if
+    /// the parties don't have enough balance.
+    pub fn settlement_transfers(
+        contract: &Contract,
+        payoffs: &[(Option<u32>, Real)],
+    ) -> ContractResult<Vec<(u32, H256, H256, Real)>> {
+        // This is called by the Scheduler so it must not panic.
+        let mut transfers: Vec<(u32, H256, H256, Real)> = Vec::new();
+        for &(currency, payoff) in payoffs {
+            let mut payoff = payoff;
+            if let Some(currency) = currency {
+                payoff = <assets::Module<T>>::round_to_precision(currency, payoff);
+            }
+            if payoff == Real::from(0) {
+                continue;
+            }
+            if payoff == Real(None) {
+                return Err("Payoff couldn't be calculated");
+            }
+            let (currency, creator_id, counterparty_id) = match (
+                currency,
+                contract.terms.creator_id,
+                contract.terms.counterparty_id,
+            ) {
+                (Some(currency), Some(creator_id), Some(counterparty_id)) => {
+                    (currency, creator_id, counterparty_id)
+                }
+                _ => return Err("Contract has no settlement currency or parties"),
+            };
+            // TODO: Real is Option<i64> but use generic_asset T::Balance
+            if payoff >= Real::from(0) {
+                transfers.push((currency, counterparty_id, creator_id, payoff.abs()));
+            } else {
+                transfers.push((currency, creator_id, counterparty_id, payoff.abs()));
+            }
+        }
+        Ok(transfers)
+    }
+
+    /// Executes transfers, either all of them or none of them if a party doesn't have enough
+    /// balance.
+    pub fn execute_transfers(transfers: Vec<(u32, H256, H256, Real)>) -> Result {
+        // Checking that the parties have enough balance for all of the transfers, so that none of
+        // them is executed otherwise.
+        let mut amounts: Vec<(u32, H256, Real)> = Vec::new();
+        for &(currency, from_address, _, amount) in transfers.iter() {
+            match amounts
+                .iter_mut()
+                .find(|total| total.0 == currency && total.1 == from_address)
+            {
+                Some(total) => total.2 = total.2 + amount,
+                None => amounts.push((currency, from_address, amount)),
+            }
+        }
+        for (currency, from_address, amount) in amounts {
+            if amount > <assets::Module<T>>::balances((currency, from_address)) {
+                return Err("Party doesn't have enough balance to settle the payoffs");
+            }
+        }
+
+        for (currency, from_address, to_address, amount) in transfers {
+            <assets::Module<T>>::transfer(from_address, to_address, currency, amount)?;
+        }
 
-        // Return Ok if successful.
         Ok(())
     }
 }
@@ -117,9 +489,15 @@ mod tests {
         type OnTimestampSet = ();
         type MinimumPeriod = MinimumPeriod;
     }
-    impl oracle::Trait for Test {}
-    impl assets::Trait for Test {}
-    impl Trait for Test {}
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
     type Assets = assets::Module<Test>;
     type Contracts = Module<Test>;
 
@@ -163,7 +541,7 @@ mod tests {
             <Contracts as Store>::Contracts::insert(id, contract.clone());
             assert_eq!(
                 contract.schedule[0],
-                Event::new(Time::from_values(2015, 01, 02, 00, 00, 00), EventType::IED)
+                ContractEvent::new(Time::from_values(2015, 01, 02, 00, 00, 00), EventType::IED)
             );
             Contracts::progress(contract.schedule[0], id);
             contract = <Contracts as Store>::Contracts::get(id);
@@ -180,7 +558,7 @@ mod tests {
             // on the contract).
             assert_eq!(
                 contract.schedule[3],
-                Event::new(Time::from_values(2015, 04, 02, 00, 00, 00), EventType::MD)
+                ContractEvent::new(Time::from_values(2015, 04, 02, 00, 00, 00), EventType::MD)
             );
             Contracts::progress(contract.schedule[3], id);
             contract = <Contracts as Store>::Contracts::get(id);
@@ -194,4 +572,154 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn progress_handles_failed_payoffs() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let creator_id = H256::random();
+            let counterparty_id = H256::random();
+            let currency = 1;
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::PAM);
+            terms.counterparty_id = Some(counterparty_id);
+            terms.creator_id = Some(creator_id);
+            terms.settlement_currency = Some(currency);
+            terms.currency = Some(currency);
+            terms.day_count_convention = Some(DayCountConvention::_30E360);
+            terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+            terms.maturity_date = Time::from_values(2015, 04, 02, 00, 00, 00);
+            terms.nominal_interest_rate = Real::from(0);
+            terms.notional_principal = Real::from(1000);
+            terms.premium_discount_at_ied = Real::from(0);
+            terms.rate_spread = Real::from(0);
+            terms.scaling_effect = None;
+            terms.grace_period = Some(Period::Days(5));
+            terms.delinquency_period = Some(Period::Months(1));
+            assert_ok!(Assets::mint(counterparty_id, currency, Real::from(1000)));
+            let contract = Contracts::deploy_pam(t0, terms).unwrap();
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+
+            // The creator can't pay the principal, so the contract is delayed instead of stuck.
+            assert_ok!(Contracts::progress(contract.schedule[0], id));
+            let contract = <Contracts as Store>::Contracts::get(id);
+            assert_eq!(
+                contract.states.contract_performance,
+                Some(ContractPerformance::DL)
+            );
+            assert_eq!(
+                contract.states.non_performing_date,
+                Time::from_values(2015, 01, 02, 00, 00, 00)
+            );
+            assert_eq!(contract.states.notional_principal, Real::from(1000));
+            assert_eq!(
+                <Contracts as Store>::Arrears::get(id),
+                vec![(Some(currency), Real::from(-1000))]
+            );
+
+            // After the delinquency period the contract defaults and its payoffs are zero.
+            assert_ok!(Contracts::progress(contract.schedule[3], id));
+            let contract = <Contracts as Store>::Contracts::get(id);
+            assert_eq!(
+                contract.states.contract_performance,
+                Some(ContractPerformance::DF)
+            );
+            assert_eq!(
+                Assets::balances((currency, counterparty_id)),
+                Real::from(1000)
+            );
+        });
+    }
+
+    #[test]
+    fn progress_settles_arrears() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let creator_id = H256::random();
+            let counterparty_id = H256::random();
+            let currency = 1;
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::PAM);
+            terms.counterparty_id = Some(counterparty_id);
+            terms.creator_id = Some(creator_id);
+            terms.settlement_currency = Some(currency);
+            terms.currency = Some(currency);
+            terms.day_count_convention = Some(DayCountConvention::_30E360);
+            terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+            terms.maturity_date = Time::from_values(2015, 04, 02, 00, 00, 00);
+            terms.nominal_interest_rate = Real::from(0);
+            terms.notional_principal = Real::from(1000);
+            terms.premium_discount_at_ied = Real::from(0);
+            terms.rate_spread = Real::from(0);
+            terms.scaling_effect = None;
+            let contract = Contracts::deploy_pam(t0, terms).unwrap();
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+
+            // Without a grace period, the delayed contract stays delayed.
+            assert_ok!(Contracts::progress(contract.schedule[0], id));
+            let event =
+                ContractEvent::new(Time::from_values(2015, 02, 02, 00, 00, 00), EventType::IP);
+            assert_ok!(Contracts::progress(event, id));
+            let contract = <Contracts as Store>::Contracts::get(id);
+            assert_eq!(
+                contract.states.contract_performance,
+                Some(ContractPerformance::DL)
+            );
+
+            // Once the creator can pay the principal it is paid at the next event, and the
+            // contract is performant again.
+            assert_ok!(Assets::mint(creator_id, currency, Real::from(1000)));
+            let event =
+                ContractEvent::new(Time::from_values(2015, 03, 02, 00, 00, 00), EventType::IP);
+            assert_ok!(Contracts::progress(event, id));
+            let contract = <Contracts as Store>::Contracts::get(id);
+            assert_eq!(
+                contract.states.contract_performance,
+                Some(ContractPerformance::PF)
+            );
+            assert_eq!(contract.states.non_performing_date, Time(None));
+            assert!(!<Contracts as Store>::Arrears::exists(id));
+            assert_eq!(
+                Assets::balances((currency, counterparty_id)),
+                Real::from(1000)
+            );
+        });
+    }
+
+    #[test]
+    fn settle_doesnt_panic() {
+        new_test_ext().execute_with(|| {
+            let mut contract = Contract::default();
+
+            // Null payoffs don't need to be settled.
+            assert_ok!(Contracts::settle(&contract, &[(None, Real::from(0))]));
+
+            // Contracts without a settlement currency or parties can't settle payoffs.
+            assert!(Contracts::settle(&contract, &[(None, Real::from(10))]).is_err());
+            assert!(Contracts::settle(&contract, &[(Some(1), Real::from(10))]).is_err());
+            contract.terms.creator_id = Some(H256::random());
+            contract.terms.counterparty_id = Some(H256::random());
+            assert!(Contracts::settle(&contract, &[(None, Real::from(10))]).is_err());
+            assert!(Contracts::settle(&contract, &[(Some(1), Real(None))]).is_err());
+
+            // Either all of the payoffs are settled or none of them.
+            let creator_id = contract.terms.creator_id.unwrap();
+            let counterparty_id = contract.terms.counterparty_id.unwrap();
+            assert_ok!(Assets::mint(creator_id, 1, Real::from(10)));
+            let payoffs = [(Some(1), Real::from(-10)), (Some(1), Real::from(-5))];
+            assert!(Contracts::settle(&contract, &payoffs).is_err());
+            assert_eq!(Assets::balances((1, creator_id)), Real::from(10));
+            assert_ok!(Assets::mint(creator_id, 1, Real::from(5)));
+            assert_ok!(Contracts::settle(&contract, &payoffs));
+            assert_eq!(Assets::balances((1, counterparty_id)), Real::from(15));
+        });
+    }
 }
@@ -0,0 +1,372 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+/// The context of the message signed by the counterparty to accept a contract, so that the
+/// signature can't be used for anything else.
+pub const ACCEPT_CONTEXT: &[u8] = b"katal/accept";
+
+// These functions implement the two-phase deployment of a contract: the creator proposes the
+// terms and the contract is only deployed once the counterparty accepts them.
+impl<T: Trait> Module<T> {
+    /// Stores the terms proposed by the creator of a contract until the counterparty accepts them
+    /// or the proposal expires. Returns the ID of the contract, which is derived from the terms.
+    pub fn propose(who: H256, mut terms: Terms, expiry: Time) -> ContractResult<H256> {
+        // Checking if the signer is the creator of the contract.
+        if Some(who) != terms.creator_id {
+            return Err("Only the creator can propose a contract");
+        }
+
+        // Checking if the expiry is in the future.
+        let now = Time::from_unix(<timestamp::Module<T>>::get().saturated_into::<u64>());
+        if expiry == Time(None) || expiry <= now {
+            return Err("Proposal expiry must be in the future");
+        }
+
+        // Deriving the contract ID. The ID given in the terms is ignored, so it can be predicted
+        // by the creator but not chosen.
+        let nonce = <Self as Store>::Nonces::get(who);
+        let id = Self::contract_id(who, &terms, nonce);
+        if <Self as Store>::Contracts::exists(id) {
+            return Err("Contract ID already exists");
+        }
+        terms.contract_id = id;
+
+        // Storing the proposal.
+        <Self as Store>::Nonces::insert(who, nonce + 1);
+        <Self as Store>::Proposals::insert(id, Proposal { terms, expiry });
+
+        // Return the contract ID if successful.
+        Ok(id)
+    }
+
+    /// Derives the ID of a contract as the Blake2 hash of its terms (without the ID), its creator
+    /// and the nonce of the creator.
+    pub fn contract_id(creator: H256, terms: &Terms, nonce: u64) -> H256 {
+        let mut terms = terms.clone();
+        terms.contract_id = H256::zero();
+        BlakeTwo256::hash_of(&(terms, creator, nonce))
+    }
+
+    /// Deploys a proposed contract, the signer must be its counterparty.
+    pub fn accept(who: H256, contract_id: H256) -> Result {
+        // Getting the proposal.
+        let proposal = Self::pending_proposal(contract_id)?;
+
+        // Checking if the signer is the counterparty of the contract.
+        if Some(who) != proposal.terms.counterparty_id {
+            return Err("Only the counterparty can accept a contract");
+        }
+
+        // Deploying the contract.
+        Self::deploy(proposal.terms)?;
+        <Self as Store>::Proposals::remove(contract_id);
+
+        // Return Ok if successful.
+        Ok(())
+    }
+
+    /// Deploys a proposed contract using a signature of its counterparty over the acceptance
+    /// message (see `acceptance_message`). This allows anyone (e.g. the creator) to submit the
+    /// acceptance of the counterparty.
+    pub fn accept_signed(contract_id: H256, signature: MultiSignature) -> Result {
+        // Getting the proposal.
+        let proposal = Self::pending_proposal(contract_id)?;
+
+        // Checking the signature against the key of the counterparty.
+        let counterparty = proposal
+            .terms
+            .counterparty_id
+            .ok_or("Contract has no counterparty")?;
+        let signer = AccountId32::from(counterparty.to_fixed_bytes());
+        let message = Self::acceptance_message(contract_id, proposal.expiry);
+        if !signature.verify(&message[..], &signer) {
+            return Err("Invalid counterparty signature");
+        }
+
+        // Deploying the contract.
+        Self::deploy(proposal.terms)?;
+        <Self as Store>::Proposals::remove(contract_id);
+
+        // Return Ok if successful.
+        Ok(())
+    }
+
+    /// Returns the message signed by the counterparty to accept a proposal: the encoded context,
+    /// genesis hash of the chain, contract ID and expiry of the proposal. The contract ID is
+    /// derived from the terms, and the genesis hash prevents replaying the signature on another
+    /// chain.
+    pub fn acceptance_message(contract_id: H256, expiry: Time) -> Vec<u8> {
+        let genesis_hash = <system::Module<T>>::block_hash(T::BlockNumber::zero());
+        (ACCEPT_CONTEXT, genesis_hash, contract_id, expiry).encode()
+    }
+
+    /// Removes a proposal that hasn't been accepted yet, the signer must be its creator.
+    pub fn withdraw(who: H256, contract_id: H256) -> Result {
+        // Getting the proposal, expired proposals can be withdrawn as well.
+        if !<Self as Store>::Proposals::exists(contract_id) {
+            return Err("Proposal doesn't exist");
+        }
+        let proposal = <Self as Store>::Proposals::get(contract_id);
+
+        // Checking if the signer is the creator of the contract.
+        if Some(who) != proposal.terms.creator_id {
+            return Err("Only the creator can withdraw a proposal");
+        }
+
+        // Removing the proposal.
+        <Self as Store>::Proposals::remove(contract_id);
+
+        // Return Ok if successful.
+        Ok(())
+    }
+
+    /// Returns the proposal with the given ID, if it exists and hasn't expired. Expired proposals
+    /// are removed.
+    fn pending_proposal(contract_id: H256) -> ContractResult<Proposal> {
+        if !<Self as Store>::Proposals::exists(contract_id) {
+            return Err("Proposal doesn't exist");
+        }
+        let proposal = <Self as Store>::Proposals::get(contract_id);
+        let now = Time::from_unix(<timestamp::Module<T>>::get().saturated_into::<u64>());
+        if proposal.expiry <= now {
+            <Self as Store>::Proposals::remove(contract_id);
+            return Err("Proposal has expired");
+        }
+        Ok(proposal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::{sr25519, Pair, H256};
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    // Returns the terms of a PAM contract between the accounts 1 and 2.
+    fn pam_terms() -> Terms {
+        let mut terms = Terms::new(H256::zero());
+        terms.counterparty_id = Some(<assets::Module<Test>>::account_id(&2));
+        terms.contract_deal_date = Time::from_values(1968, 07, 21, 02, 56, 15);
+        terms.contract_role = Some(ContractRole::RPA);
+        terms.contract_type = Some(ContractType::PAM);
+        terms.creator_id = Some(<assets::Module<Test>>::account_id(&1));
+        terms.currency = Some(1);
+        terms.day_count_convention = Some(DayCountConvention::A365);
+        terms.initial_exchange_date = Time::from_values(1970, 07, 21, 02, 56, 15);
+        terms.maturity_date = Time::from_values(1979, 07, 21, 02, 56, 15);
+        terms.nominal_interest_rate = Real::from(1000);
+        terms.notional_principal = Real(Some(50000000));
+        terms
+    }
+
+    // Registers the currency used in the terms.
+    fn create_currency() {
+        let metadata = assets::AssetMetadata {
+            symbol: b"USD".to_vec(),
+            decimals: 2,
+            ..Default::default()
+        };
+        assert_ok!(<assets::Module<Test>>::create(
+            H256::random(),
+            1,
+            Real(None),
+            metadata
+        ));
+    }
+
+    #[test]
+    fn propose_works() {
+        new_test_ext().execute_with(|| {
+            let terms = pam_terms();
+            let expiry = Time::from_values(1970, 01, 08, 00, 00, 00);
+            let creator = <assets::Module<Test>>::account_id(&1);
+
+            // The ID can be predicted before the proposal, the given ID is ignored.
+            let predicted_id = Contracts::contract_id(creator, &terms, 0);
+            let mut other_terms = terms.clone();
+            other_terms.contract_id = H256::random();
+            let result = Contracts::propose(creator, other_terms, expiry);
+            assert_eq!(result, Ok(predicted_id));
+            let proposal = <Contracts as Store>::Proposals::get(predicted_id);
+            assert_eq!(proposal.terms.contract_id, predicted_id);
+
+            // Proposing the same terms again gives a different ID.
+            let id = Contracts::propose(creator, terms.clone(), expiry).unwrap();
+            assert_ne!(id, predicted_id);
+            assert_eq!(id, Contracts::contract_id(creator, &terms, 1));
+        });
+    }
+
+    #[test]
+    fn accept_works() {
+        new_test_ext().execute_with(|| {
+            create_currency();
+            let terms = pam_terms();
+            let expiry = Time::from_values(1970, 01, 08, 00, 00, 00);
+
+            // Only the creator can propose the contract, and only with a future expiry.
+            let counterparty = <assets::Module<Test>>::account_id(&2);
+            assert!(Contracts::propose(counterparty, terms.clone(), expiry).is_err());
+            let creator = <assets::Module<Test>>::account_id(&1);
+            assert!(Contracts::propose(creator, terms.clone(), Time(None)).is_err());
+            let id = Contracts::propose(creator, terms.clone(), expiry).unwrap();
+            assert_eq!(<Contracts as Store>::Contracts::exists(id), false);
+            assert!(!<Contracts as Store>::SchedulerDay::exists());
+
+            // Only the counterparty can accept it.
+            assert!(Contracts::accept(creator, id).is_err());
+            assert_ok!(Contracts::accept(counterparty, id));
+            assert_eq!(<Contracts as Store>::Proposals::exists(id), false);
+            assert_eq!(<Contracts as Store>::Contracts::exists(id), true);
+            let day = scheduler_day(<Contracts as Store>::Contracts::get(id).schedule[0].time);
+            let event = Contracts::scheduler_bucket(day)[0];
+            assert_eq!(event.contract_id, id);
+
+            // Expired proposals can't be accepted, they are removed.
+            let id = Contracts::propose(creator, terms, expiry).unwrap();
+            <timestamp::Module<Test>>::set_timestamp(8 * 86_400);
+            assert!(Contracts::accept(counterparty, id).is_err());
+            assert_eq!(<Contracts as Store>::Proposals::exists(id), false);
+        });
+    }
+
+    #[test]
+    fn withdraw_works() {
+        new_test_ext().execute_with(|| {
+            let terms = pam_terms();
+            let expiry = Time::from_values(1970, 01, 08, 00, 00, 00);
+            let creator = <assets::Module<Test>>::account_id(&1);
+            let counterparty = <assets::Module<Test>>::account_id(&2);
+            let id = Contracts::propose(creator, terms, expiry).unwrap();
+
+            // Only the creator can withdraw the proposal.
+            assert!(Contracts::withdraw(counterparty, id).is_err());
+            assert_ok!(Contracts::withdraw(creator, id));
+            assert_eq!(<Contracts as Store>::Proposals::exists(id), false);
+            assert!(Contracts::withdraw(creator, id).is_err());
+
+            // A withdrawn proposal can't be accepted.
+            assert!(Contracts::accept(counterparty, id).is_err());
+        });
+    }
+
+    #[test]
+    fn accept_signed_works() {
+        new_test_ext().execute_with(|| {
+            create_currency();
+            // The counterparty is identified by its public key.
+            let pair = sr25519::Pair::from_seed(&[7u8; 32]);
+            let mut terms = pam_terms();
+            terms.counterparty_id = Some(H256::from(pair.public().0));
+            let creator = <assets::Module<Test>>::account_id(&1);
+            let expiry = Time::from_values(1970, 01, 08, 00, 00, 00);
+            let id = Contracts::propose(creator, terms, expiry).unwrap();
+            let terms = <Contracts as Store>::Proposals::get(id).terms;
+
+            // Signatures over other messages or from other keys are rejected.
+            let message = Contracts::acceptance_message(id, expiry);
+            let signature = MultiSignature::from(pair.sign(&terms.encode()[..]));
+            assert!(Contracts::accept_signed(id, signature).is_err());
+            let other_message = Contracts::acceptance_message(H256::random(), expiry);
+            let signature = MultiSignature::from(pair.sign(&other_message[..]));
+            assert!(Contracts::accept_signed(id, signature).is_err());
+            let other_pair = sr25519::Pair::from_seed(&[8u8; 32]);
+            let signature = MultiSignature::from(other_pair.sign(&message[..]));
+            assert!(Contracts::accept_signed(id, signature).is_err());
+
+            // The signature is only valid on this chain.
+            let genesis_hash = <system::Module<Test>>::block_hash(0);
+            let other_chain = (ACCEPT_CONTEXT, H256::random(), id, expiry).encode();
+            let signature = MultiSignature::from(pair.sign(&other_chain[..]));
+            assert!(Contracts::accept_signed(id, signature).is_err());
+
+            // The signature of the counterparty deploys the contract.
+            assert_eq!(message, (ACCEPT_CONTEXT, genesis_hash, id, expiry).encode());
+            let signature = MultiSignature::from(pair.sign(&message[..]));
+            assert_ok!(Contracts::accept_signed(id, signature));
+            assert_eq!(<Contracts as Store>::Contracts::exists(id), true);
+        });
+    }
+}
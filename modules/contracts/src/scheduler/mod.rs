@@ -14,7 +14,17 @@
 use super::*;
 
 mod scheduled_event;
+mod scheduler_cancel;
+mod scheduler_extend;
+mod scheduler_failure;
+mod scheduler_migrate;
+mod scheduler_push;
 mod scheduler_run;
 
 pub use scheduled_event::*;
+pub use scheduler_cancel::*;
+pub use scheduler_extend::*;
+pub use scheduler_failure::*;
+pub use scheduler_migrate::*;
+pub use scheduler_push::*;
 pub use scheduler_run::*;
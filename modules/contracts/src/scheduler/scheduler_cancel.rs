@@ -0,0 +1,276 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+/// Tells if an event terminates a contract early, that is a termination or a prepayment of the
+/// full notional principal. The contract is given in its state after the event.
+pub fn terminates(event: ContractEvent, contract: &Contract) -> bool {
+    match event.event_type {
+        EventType::TD => true,
+        EventType::PP => contract.states.notional_principal == Real::from(0),
+        _ => false,
+    }
+}
+
+// This function removes events from the Scheduler.
+impl<T: Trait> Module<T> {
+    /// Removes the pending scheduled event of a contract from the Scheduler, if it has one.
+    pub fn scheduler_cancel(contract_id: H256) {
+        if !<Self as Store>::PendingEvents::exists(contract_id) {
+            return;
+        }
+        let slot = <Self as Store>::PendingEvents::take(contract_id);
+        <Self as Store>::ScheduledEvents::remove(slot);
+        <Self as Store>::SchedulerPending::mutate(slot.0, |pending| {
+            *pending = pending.saturating_sub(1)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn scheduler_cancel_works() {
+        new_test_ext().execute_with(|| {
+            // The Scheduler starts on the day the contract is deployed.
+            <timestamp::Module<Test>>::set_timestamp(1_420_070_400);
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::PAM);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.settlement_currency = Some(1);
+            terms.day_count_convention = Some(DayCountConvention::_30E360);
+            terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+            terms.maturity_date = Time::from_values(2015, 04, 02, 00, 00, 00);
+            terms.nominal_interest_rate = Real::from(0);
+            terms.notional_principal = Real::from(1000);
+            terms.rate_spread = Real::from(0);
+            assert_ok!(<assets::Module<Test>>::mint(
+                terms.creator_id.unwrap(),
+                terms.currency.unwrap(),
+                terms.notional_principal,
+            ));
+            assert_ok!(<assets::Module<Test>>::mint(
+                terms.counterparty_id.unwrap(),
+                terms.currency.unwrap(),
+                Real::from(1000),
+            ));
+
+            // Note: The price at termination is only kept by the deployment if there is a
+            // termination date.
+            let mut contract = Contracts::deploy_pam(t0, terms).unwrap();
+            contract.terms.price_at_termination_date = Real::from(1000);
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+            Contracts::scheduler_push(ScheduledEvent {
+                time: contract.schedule[0].time,
+                contract_id: id,
+                index: 0,
+            });
+
+            // After the initial exchange, the maturity is pending in the Scheduler.
+            assert_ok!(Contracts::scheduler_run(Time::from_values(
+                2015, 01, 02, 00, 00, 00
+            )));
+            let md_day = scheduler_day(Time::from_values(2015, 04, 02, 00, 00, 00));
+            assert_eq!(<Contracts as Store>::PendingEvents::get(id).0, md_day);
+            assert_eq!(Contracts::scheduler_bucket(md_day).len(), 1);
+
+            // The contract is terminated early.
+            let td_time = Time::from_values(2015, 02, 01, 00, 00, 00);
+            assert!(terminates(
+                ContractEvent::new(td_time, EventType::TD),
+                &Contract::default()
+            ));
+            assert_ok!(Contracts::progress(
+                ContractEvent::new(td_time, EventType::TD),
+                id
+            ));
+
+            // Its remaining events were removed from the schedule and from the Scheduler.
+            let terminated_contract = <Contracts as Store>::Contracts::get(id);
+            assert!(terminated_contract.schedule.len() < contract.schedule.len());
+            assert!(terminated_contract
+                .schedule
+                .iter()
+                .all(|event| event.time <= td_time));
+            assert!(!<Contracts as Store>::PendingEvents::exists(id));
+            assert!(Contracts::scheduler_bucket(md_day).is_empty());
+
+            // The maturity is never executed.
+            assert_ok!(Contracts::scheduler_run(Time::from_values(
+                2015, 05, 01, 00, 00, 00
+            )));
+            assert_eq!(
+                <Contracts as Store>::Contracts::get(id).states.status_date,
+                td_time
+            );
+        });
+    }
+
+    #[test]
+    fn full_prepayment_cancels_the_remaining_events() {
+        new_test_ext().execute_with(|| {
+            <timestamp::Module<Test>>::set_timestamp(1_420_070_400);
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::PAM);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.settlement_currency = Some(1);
+            terms.day_count_convention = Some(DayCountConvention::_30E360);
+            terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+            terms.maturity_date = Time::from_values(2015, 04, 02, 00, 00, 00);
+            terms.nominal_interest_rate = Real::from(0);
+            terms.notional_principal = Real::from(1000);
+            terms.rate_spread = Real::from(0);
+            assert_ok!(<assets::Module<Test>>::mint(
+                terms.creator_id.unwrap(),
+                terms.currency.unwrap(),
+                terms.notional_principal,
+            ));
+            assert_ok!(<assets::Module<Test>>::mint(
+                terms.counterparty_id.unwrap(),
+                terms.currency.unwrap(),
+                Real::from(1000),
+            ));
+
+            let contract = Contracts::deploy_pam(t0, terms).unwrap();
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+            Contracts::scheduler_push(ScheduledEvent {
+                time: contract.schedule[0].time,
+                contract_id: id,
+                index: 0,
+            });
+            assert_ok!(Contracts::scheduler_run(Time::from_values(
+                2015, 01, 02, 00, 00, 00
+            )));
+            let md_day = scheduler_day(Time::from_values(2015, 04, 02, 00, 00, 00));
+
+            // A partial prepayment reduces the notional principal, the contract goes on.
+            let event = ContractEvent::with_amount(
+                Time::from_values(2015, 02, 01, 00, 00, 00),
+                EventType::PP,
+                Real::from(400),
+            );
+            assert_ok!(Contracts::progress(event, id));
+            let prepaid_contract = <Contracts as Store>::Contracts::get(id);
+            assert!(!terminates(event, &prepaid_contract));
+            assert_eq!(prepaid_contract.states.notional_principal, Real::from(600));
+            assert_eq!(prepaid_contract.schedule.len(), contract.schedule.len());
+            assert_eq!(Contracts::scheduler_bucket(md_day).len(), 1);
+
+            // Prepaying more than the remaining notional principal only prepays the rest of it,
+            // which terminates the contract.
+            let pp_time = Time::from_values(2015, 03, 01, 00, 00, 00);
+            let event = ContractEvent::with_amount(pp_time, EventType::PP, Real::from(1500));
+            assert_ok!(Contracts::progress(event, id));
+            let prepaid_contract = <Contracts as Store>::Contracts::get(id);
+            assert_eq!(prepaid_contract.states.notional_principal, Real::from(0));
+            assert!(terminates(event, &prepaid_contract));
+            assert!(prepaid_contract
+                .schedule
+                .iter()
+                .all(|event| event.time <= pp_time));
+            assert!(!<Contracts as Store>::PendingEvents::exists(id));
+            assert!(Contracts::scheduler_bucket(md_day).is_empty());
+            assert_eq!(
+                <assets::Module<Test>>::balances((1, prepaid_contract.terms.creator_id.unwrap())),
+                Real::from(1000)
+            );
+        });
+    }
+}
@@ -0,0 +1,242 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+/// The number of days covered by each piece of the schedule of a contract without a maturity date.
+pub const SCHEDULE_WINDOW: u16 = 366;
+
+/// Appends the next piece of the schedule to a contract without a maturity date (CLM, UMP and
+/// STK). Other contracts, and open-ended contracts that have already been terminated, are returned
+/// unchanged.
+pub fn scheduler_extend(mut contract: Contract) -> ContractResult<Contract> {
+    if contract.terms.contract_type != Some(ContractType::CLM)
+        && contract.terms.contract_type != Some(ContractType::UMP)
+        && contract.terms.contract_type != Some(ContractType::STK)
+    {
+        return Ok(contract);
+    }
+    if contract.terms.maturity_date != Time(None)
+        || contract.terms.termination_date != Time(None)
+        || contract.states.time_at_maturity_date != Time(None)
+    {
+        return Ok(contract);
+    }
+
+    let from = match contract.schedule.last() {
+        Some(event) => event.time,
+        None => contract.states.status_date,
+    };
+    let mut events = open_ended_schedule(&contract.terms, from)?;
+    contract.schedule.append(&mut events);
+
+    Ok(contract)
+}
+
+/// Creates the next piece of the schedule of a contract without a maturity date, starting after
+/// `from`. It spans as many windows as needed to contain at least one event, unless the contract
+/// has no cyclic events at all.
+pub fn open_ended_schedule(terms: &Terms, from: Time) -> ContractResult<Vec<ContractEvent>> {
+    let cyclic;
+    if terms.contract_type == Some(ContractType::STK) {
+        cyclic = terms.cycle_of_dividend.is_some();
+    } else {
+        let fee = terms.fee_rate != Real(None) && terms.fee_rate != Real::from(0);
+        cyclic = (fee && terms.cycle_of_fee.is_some())
+            || terms.cycle_of_interest_payment.is_some()
+            || terms.cycle_of_rate_reset.is_some();
+    }
+    if !cyclic {
+        return Ok(Vec::new());
+    }
+
+    let mut to = from;
+    loop {
+        to = to.add_days(SCHEDULE_WINDOW);
+        let schedule = open_ended_events(terms, from, to)?;
+        if !schedule.is_empty() {
+            return Ok(schedule);
+        }
+    }
+}
+
+/// Creates the cyclic events (fee payment, interest capitalization and rate reset, or dividend
+/// payment for stocks) of a contract without a maturity date that fall in the interval
+/// (`from`, `to`].
+pub fn open_ended_events(
+    terms: &Terms,
+    from: Time,
+    to: Time,
+) -> ContractResult<Vec<ContractEvent>> {
+    let mut schedule: Vec<ContractEvent> = Vec::new();
+
+    // Dividend payment event
+    if terms.contract_type == Some(ContractType::STK) {
+        // Note: The dividend cycle is a period, since the schedule of a stock has no end date and
+        // thus no stub.
+        let cycle = match terms.cycle_of_dividend {
+            Some(Period::Days(int)) => Some(Cycle::Days(int, true)),
+            Some(Period::Months(int)) => Some(Cycle::Months(int, true)),
+            Some(Period::Years(int)) => Some(Cycle::Years(int, true)),
+            None => None,
+        };
+        let vec = utilities::open_schedule(
+            terms.cycle_anchor_date_of_dividend,
+            from,
+            to,
+            cycle,
+            terms.end_of_month_convention,
+        )?;
+
+        for t in vec {
+            let event = ContractEvent::new(t, EventType::DV);
+            schedule.push(event);
+        }
+
+        return Ok(schedule);
+    }
+
+    // Fee payment event
+    if terms.fee_rate == Real(None) || terms.fee_rate == Real::from(0) {
+    } else {
+        let mut s: Time = Time(None);
+        if terms.cycle_anchor_date_of_fee == Time(None) {
+            s = utilities::sum_cycle(
+                terms.initial_exchange_date,
+                terms.cycle_of_fee,
+                terms.end_of_month_convention,
+            );
+        } else {
+            s = terms.cycle_anchor_date_of_fee;
+        }
+
+        let vec = utilities::open_schedule(
+            s,
+            from,
+            to,
+            terms.cycle_of_fee,
+            terms.end_of_month_convention,
+        )?;
+
+        for t in vec {
+            let event = ContractEvent::new(t, EventType::FP);
+            schedule.push(event);
+        }
+    }
+
+    // Interest capitalization event
+    // Note: Contracts without a maturity date capitalize the interest on the interest payment cycle.
+    let mut s: Time = Time(None);
+    if terms.cycle_anchor_date_of_interest_payment == Time(None) {
+        s = utilities::sum_cycle(
+            terms.initial_exchange_date,
+            terms.cycle_of_interest_payment,
+            terms.end_of_month_convention,
+        );
+    } else {
+        s = terms.cycle_anchor_date_of_interest_payment;
+    }
+
+    let vec = utilities::open_schedule(
+        s,
+        from,
+        to,
+        terms.cycle_of_interest_payment,
+        terms.end_of_month_convention,
+    )?;
+
+    for t in vec {
+        let event = ContractEvent::new(t, EventType::IPCI);
+        schedule.push(event);
+    }
+
+    // Rate reset variable event
+    let mut s: Time = Time(None);
+    if terms.cycle_anchor_date_of_rate_reset == Time(None) {
+        s = utilities::sum_cycle(
+            terms.initial_exchange_date,
+            terms.cycle_of_rate_reset,
+            terms.end_of_month_convention,
+        );
+    } else {
+        s = terms.cycle_anchor_date_of_rate_reset;
+    }
+
+    let vec = utilities::open_schedule(
+        s,
+        from,
+        to,
+        terms.cycle_of_rate_reset,
+        terms.end_of_month_convention,
+    )?;
+
+    for t in vec {
+        let event = ContractEvent::new(t, EventType::RR);
+        schedule.push(event);
+    }
+
+    // Ordering the schedule
+    schedule.sort_unstable();
+
+    Ok(schedule)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_ended_schedule_works() {
+        let id = H256::random();
+        let mut terms = Terms::new(id);
+        terms.contract_type = Some(ContractType::CLM);
+        terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+        terms.nominal_interest_rate = Real::from(0);
+
+        // Without cycles there are no events.
+        let from = terms.initial_exchange_date;
+        assert_eq!(open_ended_schedule(&terms, from), Ok(Vec::new()));
+
+        // The window is widened until it contains an event.
+        terms.cycle_anchor_date_of_interest_payment = Time::from_values(2016, 06, 02, 00, 00, 00);
+        terms.cycle_of_interest_payment = Some(Cycle::Years(1, true));
+        assert_eq!(
+            open_ended_schedule(&terms, from),
+            Ok(vec![ContractEvent::new(
+                Time::from_values(2016, 06, 02, 00, 00, 00),
+                EventType::IPCI
+            )])
+        );
+
+        // Each piece of the schedule is extended from the last event of the contract.
+        let mut contract = Contract {
+            terms: terms,
+            states: States::new(),
+            schedule: vec![ContractEvent::new(from, EventType::IED)],
+        };
+        contract = scheduler_extend(contract).unwrap();
+        contract = scheduler_extend(contract).unwrap();
+        assert_eq!(
+            contract.schedule,
+            vec![
+                ContractEvent::new(from, EventType::IED),
+                ContractEvent::new(Time::from_values(2016, 06, 02, 00, 00, 00), EventType::IPCI),
+                ContractEvent::new(Time::from_values(2017, 06, 02, 00, 00, 00), EventType::IPCI),
+            ]
+        );
+
+        // Terminated contracts are not extended.
+        contract.states.time_at_maturity_date = Time::from_values(2017, 07, 02, 00, 00, 00);
+        assert_eq!(scheduler_extend(contract.clone()), Ok(contract));
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+/// The number of times the Scheduler tries to execute the event of a failing contract before it
+/// halts the contract. The waiting time doubles after every attempt (1, 2, 4, ... days).
+pub const MAX_SCHEDULER_ATTEMPTS: u32 = 5;
+
+// This struct records the failures of a contract in the Scheduler.
+#[derive(Clone, Decode, Encode, Default, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct SchedulerFailure {
+    // The scheduled event that failed.
+    pub scheduled_event: ScheduledEvent,
+    // The number of consecutive failed attempts.
+    pub attempts: u32,
+    // The error returned by the last attempt.
+    pub error: Vec<u8>,
+}
+
+impl SchedulerFailure {
+    /// Returns true if the contract isn't retried anymore by the Scheduler.
+    pub fn is_halted(&self) -> bool {
+        self.attempts >= MAX_SCHEDULER_ATTEMPTS
+    }
+}
+
+// These functions handle the contracts that fail in the Scheduler.
+impl<T: Trait> Module<T> {
+    /// Records a failed attempt to execute a scheduled event and schedules the next attempt, unless
+    /// the contract has to be halted.
+    pub fn scheduler_failure(now: Time, mut scheduled_event: ScheduledEvent, error: &'static str) {
+        let contract_id = scheduled_event.contract_id;
+        let mut failure = <Self as Store>::SchedulerFailures::get(contract_id);
+        failure.scheduled_event = scheduled_event;
+        failure.attempts += 1;
+        failure.error = error.as_bytes().to_vec();
+
+        // Retrying with an exponential backoff.
+        if !failure.is_halted() {
+            scheduled_event.time = now.add_days(1 << (failure.attempts - 1));
+            Self::scheduler_push(scheduled_event);
+        }
+
+        Self::deposit_event(RawEvent::<T::AccountId>::SchedulerFailed(
+            contract_id,
+            failure.attempts,
+            failure.error.clone(),
+        ));
+        <Self as Store>::SchedulerFailures::insert(contract_id, failure);
+    }
+
+    /// Puts a contract halted by the Scheduler back into the Scheduler, its failed event is
+    /// retried immediately.
+    pub fn scheduler_resume(now: Time, contract_id: H256) -> Result {
+        let failure = <Self as Store>::SchedulerFailures::get(contract_id);
+        if !failure.is_halted() {
+            return Err("Contract is not halted by the Scheduler");
+        }
+
+        let mut scheduled_event = failure.scheduled_event;
+        scheduled_event.time = now;
+        Self::scheduler_push(scheduled_event);
+        <Self as Store>::SchedulerFailures::remove(contract_id);
+
+        // Return Ok if successful.
+        Ok(())
+    }
+}
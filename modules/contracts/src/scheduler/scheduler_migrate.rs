@@ -0,0 +1,151 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+// This function migrates the storage of earlier versions of the Scheduler.
+impl<T: Trait> Module<T> {
+    /// Moves the events of the binary heap used by earlier versions of the Scheduler to the
+    /// buckets, and removes the heap. It only moves events in the first block after the runtime
+    /// upgrade, afterwards the heap doesn't exist anymore. The contracts of these events are
+    /// migrated to the current layout as well.
+    pub fn scheduler_migrate() {
+        if let Some(heap) = <Self as Store>::Scheduler::take() {
+            // Note: A contract only has one pending event, the earliest one is kept in case the
+            // heap has several of them.
+            let mut scheduled_events = heap.0;
+            scheduled_events.sort();
+            for scheduled_event in scheduled_events.into_iter().rev() {
+                // Note: The contracts of the heap exist, there is nothing to do if they are
+                // already in the current layout.
+                let _ = Self::contract_migrate(scheduled_event.contract_id);
+                Self::scheduler_push(scheduled_event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn scheduler_migrate_works() {
+        new_test_ext().execute_with(|| {
+            <timestamp::Module<Test>>::set_timestamp(1_420_070_400);
+            let today = scheduler_day(Time::from_values(2015, 01, 01, 00, 00, 00));
+
+            // The heap of an earlier version of the Scheduler.
+            let first_event = ScheduledEvent {
+                time: Time::from_values(2015, 01, 02, 00, 00, 00),
+                contract_id: H256::random(),
+                index: 0,
+            };
+            let second_event = ScheduledEvent {
+                time: Time::from_values(2015, 01, 03, 00, 00, 00),
+                contract_id: H256::random(),
+                index: 2,
+            };
+            let mut heap = MinHeap::new();
+            heap.push(second_event);
+            heap.push(first_event);
+            <Contracts as Store>::Scheduler::put(heap);
+
+            // Its events are moved to the buckets.
+            Contracts::scheduler_migrate();
+            assert!(!<Contracts as Store>::Scheduler::exists());
+            assert_eq!(Contracts::scheduler_bucket(today + 1), vec![first_event]);
+            assert_eq!(Contracts::scheduler_bucket(today + 2), vec![second_event]);
+            assert_eq!(
+                Contracts::pending_event(second_event.contract_id),
+                Some(second_event)
+            );
+
+            // Nothing happens once the heap is gone.
+            Contracts::scheduler_migrate();
+            assert_eq!(Contracts::scheduler_bucket(today + 1), vec![first_event]);
+        });
+    }
+}
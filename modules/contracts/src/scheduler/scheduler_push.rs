@@ -0,0 +1,204 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+/// Returns the day (counted from the unix epoch) in which the given time falls. It is used to
+/// bucket the scheduled events, times before the epoch fall on day 0.
+pub fn scheduler_day(time: Time) -> u32 {
+    let epoch = Time::from_values(1970, 01, 01, 00, 00, 00);
+    match Time::diff_days(epoch, time) {
+        Some(days) => days as u32,
+        None => 0,
+    }
+}
+
+// This function adds an event to the Scheduler.
+impl<T: Trait> Module<T> {
+    /// Adds a scheduled event to the bucket of the day in which it is due. Events due before the
+    /// first day that the Scheduler hasn't completed yet are added to that day instead, so that
+    /// they are still executed. A contract has at most one scheduled event, so the pending
+    /// scheduled event of the contract (if any) is replaced.
+    pub fn scheduler_push(event: ScheduledEvent) {
+        Self::scheduler_cancel(event.contract_id);
+
+        if !<Self as Store>::SchedulerDay::exists() {
+            let now = Time::from_unix(<timestamp::Module<T>>::get().saturated_into::<u64>());
+            <Self as Store>::SchedulerDay::put(scheduler_day(now));
+        }
+        let day = scheduler_day(event.time).max(<Self as Store>::SchedulerDay::get());
+        let slot = <Self as Store>::SchedulerSlots::get(day);
+        <Self as Store>::SchedulerSlots::insert(day, slot + 1);
+        <Self as Store>::SchedulerPending::mutate(day, |pending| *pending += 1);
+        <Self as Store>::ScheduledEvents::insert((day, slot), event);
+        <Self as Store>::PendingEvents::insert(event.contract_id, (day, slot));
+    }
+
+    /// Returns the pending scheduled event of a contract, if it has one.
+    pub fn pending_event(contract_id: H256) -> Option<ScheduledEvent> {
+        if !<Self as Store>::PendingEvents::exists(contract_id) {
+            return None;
+        }
+        Some(<Self as Store>::ScheduledEvents::get(
+            <Self as Store>::PendingEvents::get(contract_id),
+        ))
+    }
+
+    /// Returns the scheduled events in the bucket of a day, in the order they were added.
+    pub fn scheduler_bucket(day: u32) -> Vec<ScheduledEvent> {
+        let cursor = <Self as Store>::SchedulerCursor::get(day);
+        (cursor..<Self as Store>::SchedulerSlots::get(day))
+            .filter(|slot| <Self as Store>::ScheduledEvents::exists((day, *slot)))
+            .map(|slot| <Self as Store>::ScheduledEvents::get((day, slot)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn scheduler_day_works() {
+        assert_eq!(
+            scheduler_day(Time::from_values(1970, 01, 01, 23, 59, 59)),
+            0
+        );
+        assert_eq!(
+            scheduler_day(Time::from_values(1970, 01, 02, 00, 00, 00)),
+            1
+        );
+        assert_eq!(
+            scheduler_day(Time::from_values(2015, 01, 01, 12, 00, 00)),
+            16436
+        );
+        assert_eq!(
+            scheduler_day(Time::from_values(1969, 07, 20, 20, 17, 00)),
+            0
+        );
+        assert_eq!(scheduler_day(Time(None)), 0);
+    }
+
+    #[test]
+    fn scheduler_push_works() {
+        new_test_ext().execute_with(|| {
+            // The Scheduler starts at the current day.
+            <timestamp::Module<Test>>::set_timestamp(1_420_070_400);
+            let today = scheduler_day(Time::from_values(2015, 01, 01, 00, 00, 00));
+
+            // Events are added to the bucket of their day.
+            let event = ScheduledEvent {
+                time: Time::from_values(2015, 01, 03, 12, 00, 00),
+                contract_id: H256::random(),
+                index: 0,
+            };
+            Contracts::scheduler_push(event);
+            assert_eq!(<Contracts as Store>::SchedulerDay::get(), today);
+            assert_eq!(Contracts::scheduler_bucket(today + 2), vec![event]);
+            assert_eq!(Contracts::pending_event(event.contract_id), Some(event));
+
+            // Past events are added to the first day that isn't completed.
+            let past_event = ScheduledEvent {
+                time: Time::from_values(2014, 01, 01, 00, 00, 00),
+                contract_id: H256::random(),
+                index: 3,
+            };
+            Contracts::scheduler_push(past_event);
+            assert_eq!(Contracts::scheduler_bucket(today), vec![past_event]);
+
+            // Pushing another event of the same contract replaces its pending event.
+            let mut next_event = event;
+            next_event.time = Time::from_values(2015, 01, 04, 12, 00, 00);
+            next_event.index = 1;
+            Contracts::scheduler_push(next_event);
+            assert!(Contracts::scheduler_bucket(today + 2).is_empty());
+            assert_eq!(Contracts::scheduler_bucket(today + 3), vec![next_event]);
+            assert_eq!(
+                <Contracts as Store>::PendingEvents::get(event.contract_id),
+                (today + 3, 0)
+            );
+        });
+    }
+}
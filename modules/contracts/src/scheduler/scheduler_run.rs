@@ -16,50 +16,171 @@ use super::*;
 // This function executes all events for which the time has come.
 impl<T: Trait> Module<T> {
     pub fn scheduler_run(now: Time) -> Result {
-        // Get the current Scheduler Heap from storage.
-        let mut heap = <Self as Store>::Scheduler::get();
+        // The Scheduler goes through the buckets from the first day that it hasn't completed yet
+        // until the current day. Only the buckets of those days are read.
+        let today = scheduler_day(now);
+        let mut day = today;
+        if <Self as Store>::SchedulerDay::exists() {
+            day = <Self as Store>::SchedulerDay::get();
+        }
 
-        // This loop goes through every scheduled event that is smaller than the
-        // current time.
-        while heap.peek().is_some() && now >= heap.peek().unwrap().time {
-            let mut scheduled_event = heap.pop().unwrap();
+        // The number of events that can still be executed in this block. The events left over
+        // are executed in the next blocks, in the same order.
+        let mut budget = <Self as Store>::MaxEventsPerBlock::get();
+        let mut backlog = 0;
 
-            // Get the contract state of the ACTUS contract and the corresponding
-            // contract event type to be executed.
-            let mut contract = <Self as Store>::Contracts::get(scheduled_event.contract_id);
-            let event = contract.schedule[scheduled_event.index as usize];
+        loop {
+            // Events scheduled while executing this day can't be added to earlier days.
+            <Self as Store>::SchedulerDay::put(day);
 
-            // Make the ACTUS contract progress.
-            <Module<T>>::progress(event, scheduled_event.contract_id)?;
-
-            // This loop executes the remaining events of the current contract for which
-            // the time has come. This is more efficient than just pushing the next event
-            // to the Scheduler heap.
-            scheduled_event.index += 1;
-            while scheduled_event.index < contract.schedule.len() as u32 {
-                // Get the next event for this contract.
-                let event = contract.schedule[scheduled_event.index as usize];
-                // Compare the event's time with the current time.
-                if now >= event.time {
-                    // Make the ACTUS contract progress.
-                    <Module<T>>::progress(event, scheduled_event.contract_id)?;
-                    // Increment the index.
-                    scheduled_event.index += 1;
-                } else {
-                    // Update the time for the scheduled event and push it to the heap.
-                    scheduled_event.time = event.time;
-                    heap.push(scheduled_event);
+            // Execute the events of this day for which the time has come, in the order they were
+            // scheduled. Events added to this day while executing them (e.g. the exercise of a
+            // credit enhancement) are executed afterwards. The slots before the cursor are empty,
+            // it is moved forward as long as the events before it have been executed, so that
+            // the executed events aren't read again.
+            let mut cursor = <Self as Store>::SchedulerCursor::get(day);
+            let mut slot = cursor;
+            let mut waiting = 0;
+            while slot < <Self as Store>::SchedulerSlots::get(day) {
+                if <Self as Store>::ScheduledEvents::exists((day, slot)) {
+                    let scheduled_event = <Self as Store>::ScheduledEvents::get((day, slot));
+                    if now < scheduled_event.time {
+                        // Events later in the current day are kept in their slot.
+                        waiting += 1;
+                        slot += 1;
+                        continue;
+                    }
+                    if budget == 0 {
+                        // Stop since the limit of events was reached and this day still has due
+                        // events.
+                        backlog = <Self as Store>::SchedulerPending::get(day);
+                        break;
+                    }
+                    Self::scheduler_progress(now, scheduled_event, &mut budget);
+                } else if waiting > 0 && waiting >= <Self as Store>::SchedulerPending::get(day) {
+                    // Only the events later in the day are left, the slots after them are empty.
                     break;
                 }
+                if cursor == slot {
+                    cursor += 1;
+                }
+                slot += 1;
+            }
+            <Self as Store>::SchedulerCursor::insert(day, cursor);
+            if backlog > 0 {
+                break;
+            }
+
+            // Note: The current day might still have events later in the day.
+            if day >= today {
+                break;
             }
+            // All the events of a past day were executed, so its bucket is empty.
+            <Self as Store>::SchedulerSlots::remove(day);
+            <Self as Store>::SchedulerCursor::remove(day);
+            <Self as Store>::SchedulerPending::remove(day);
+            day += 1;
+        }
+
+        // Storing the backlog, for monitoring.
+        <Self as Store>::SchedulerBacklog::put(backlog);
+        if backlog > 0 {
+            Self::deposit_event(RawEvent::<T::AccountId>::SchedulerBacklog(day, backlog));
         }
 
-        // Put the Scheduler Heap into storage.
-        <Self as Store>::Scheduler::put(heap);
+        // Return Ok.
+        Ok(())
+    }
+
+    /// Sets the maximum number of events that the Scheduler executes in a block.
+    pub fn set_max_events_per_block(max_events: u32) -> Result {
+        if max_events == 0 {
+            return Err("The Scheduler must be able to execute at least one event per block");
+        }
+        <Self as Store>::MaxEventsPerBlock::put(max_events);
 
         // Return Ok.
         Ok(())
     }
+
+    /// Executes the events of a contract for which the time has come, starting from the given
+    /// scheduled event, and schedules its next event. Each executed event uses up one unit of the
+    /// budget, once it is used up the contract continues in the next block. A contract that fails
+    /// is retried later, without stopping the Scheduler.
+    fn scheduler_progress(now: Time, mut scheduled_event: ScheduledEvent, budget: &mut u32) {
+        // The scheduled event is being executed, so it is removed from the Scheduler until the
+        // next event of the contract is scheduled.
+        Self::scheduler_cancel(scheduled_event.contract_id);
+
+        // Child contracts are progressed by their parent contract, so their own events are
+        // dropped from the Scheduler.
+        if <Self as Store>::ParentContracts::exists(scheduled_event.contract_id) {
+            return;
+        }
+
+        // This loop executes the events of the current contract for which the time has come.
+        // This is more efficient than pushing every single event to the Scheduler.
+        loop {
+            // Get the contract state of the ACTUS contract. It is read again at every
+            // iteration since progressing the contract may change it.
+            let mut contract = <Self as Store>::Contracts::get(scheduled_event.contract_id);
+
+            // Contracts without a maturity date get the next piece of their schedule once
+            // the current one has been used up.
+            if scheduled_event.index >= contract.schedule.len() as u32 {
+                contract = match scheduler_extend(contract) {
+                    Ok(contract) => contract,
+                    Err(error) => {
+                        Self::scheduler_failure(now, scheduled_event, error);
+                        break;
+                    }
+                };
+                if scheduled_event.index >= contract.schedule.len() as u32 {
+                    break;
+                }
+                <Self as Store>::Contracts::insert(scheduled_event.contract_id, contract.clone());
+            }
+
+            // Get the next event for this contract.
+            let event = contract.schedule[scheduled_event.index as usize];
+
+            // Events after the maturity of the contract (which might have been brought
+            // forward by a user initiated event) are not executed.
+            if contract.states.time_at_maturity_date != Time(None)
+                && event.time > contract.states.time_at_maturity_date
+            {
+                break;
+            }
+
+            // Compare the event's time with the current time.
+            if now >= event.time && *budget == 0 {
+                // Leave the event for the next block.
+                scheduled_event.time = event.time;
+                Self::scheduler_push(scheduled_event);
+                break;
+            } else if now >= event.time {
+                // Make the ACTUS contract progress.
+                *budget -= 1;
+                if let Err(error) = <Module<T>>::progress(event, scheduled_event.contract_id) {
+                    Self::scheduler_failure(now, scheduled_event, error);
+                    break;
+                }
+                <Self as Store>::SchedulerFailures::remove(scheduled_event.contract_id);
+                // Increment the index.
+                scheduled_event.index += 1;
+                // Terminated contracts have no more events.
+                let contract = <Self as Store>::Contracts::get(scheduled_event.contract_id);
+                if terminates(event, &contract) {
+                    break;
+                }
+            } else {
+                // Update the time for the scheduled event and add it to the Scheduler.
+                scheduled_event.time = event.time;
+                Self::scheduler_push(scheduled_event);
+                break;
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -118,9 +239,15 @@ mod tests {
         type OnTimestampSet = ();
         type MinimumPeriod = MinimumPeriod;
     }
-    impl oracle::Trait for Test {}
-    impl assets::Trait for Test {}
-    impl Trait for Test {}
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
     type Contracts = Module<Test>;
 
     // This function basically just builds a genesis storage key/value store according to
@@ -135,6 +262,8 @@ mod tests {
     #[test]
     fn scheduler_run_works() {
         new_test_ext().execute_with(|| {
+            // The Scheduler starts on the day the contract is deployed.
+            <timestamp::Module<Test>>::set_timestamp(1_420_070_400);
             let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
             let id = H256::random();
             let mut terms = Terms::new(id);
@@ -168,9 +297,7 @@ mod tests {
                 contract_id: id,
                 index: 0,
             };
-            let mut heap = MinHeap::new();
-            heap.push(event);
-            <Contracts as Store>::Scheduler::put(heap);
+            Contracts::scheduler_push(event);
 
             let result = Contracts::scheduler_run(Time::from_values(2015, 01, 02, 00, 00, 05));
             let progressed_contract = <Contracts as Store>::Contracts::get(id);
@@ -183,9 +310,433 @@ mod tests {
                 Real::from(0)
             );
             assert_eq!(progressed_contract.states.accrued_interest, Real::from(0));
-            let event = <Contracts as Store>::Scheduler::get().pop().unwrap();
-            assert_eq!(event.time, Time::from_values(2015, 04, 02, 00, 00, 00));
+            let time = Time::from_values(2015, 04, 02, 00, 00, 00);
+            let event = Contracts::scheduler_bucket(scheduler_day(time))[0];
+            assert_eq!(event.time, time);
             assert_eq!(event.index, 1);
+
+            // The executed event was removed from its bucket.
+            let day = scheduler_day(Time::from_values(2015, 01, 02, 00, 00, 00));
+            assert!(Contracts::scheduler_bucket(day).is_empty());
+            assert_eq!(<Contracts as Store>::SchedulerDay::get(), day);
+        });
+    }
+
+    #[test]
+    fn scheduler_run_catches_up_missed_events() {
+        new_test_ext().execute_with(|| {
+            // The Scheduler starts on the day the contract is deployed.
+            <timestamp::Module<Test>>::set_timestamp(1_420_070_400);
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::PAM);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.settlement_currency = Some(1);
+            terms.day_count_convention = Some(DayCountConvention::_30E360);
+            terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+            terms.maturity_date = Time::from_values(2015, 04, 02, 00, 00, 00);
+            terms.nominal_interest_rate = Real::from(0);
+            terms.notional_principal = Real::from(1000);
+            terms.rate_spread = Real::from(0);
+            assert_ok!(<assets::Module<Test>>::mint(
+                terms.creator_id.unwrap(),
+                terms.currency.unwrap(),
+                terms.notional_principal,
+            ));
+
+            let contract = Contracts::deploy_pam(t0, terms).unwrap();
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+            Contracts::scheduler_push(ScheduledEvent {
+                time: contract.schedule[0].time,
+                contract_id: id,
+                index: 0,
+            });
+
+            // The chain doesn't produce blocks until after the maturity of the contract, all of
+            // its events are then executed in the same block.
+            <system::Module<Test>>::set_block_number(10);
+            let now = Time::from_values(2015, 05, 01, 00, 00, 00);
+            assert_ok!(Contracts::scheduler_run(now));
+
+            // The history keeps the time the events were scheduled for, in order.
+            let history = Contracts::executed_events(id);
+            assert_eq!(history.len(), contract.schedule.len());
+            for (executed_event, event) in history.iter().zip(contract.schedule.iter()) {
+                assert_eq!(executed_event.event, *event);
+                assert_eq!(executed_event.block_number, 10);
+            }
+            assert_eq!(history[0].event.event_type, EventType::IED);
+            assert_eq!(
+                history[0].event.time,
+                Time::from_values(2015, 01, 02, 00, 00, 00)
+            );
+            assert_eq!(
+                history[history.len() - 1].event.time,
+                Time::from_values(2015, 04, 02, 00, 00, 00)
+            );
+        });
+    }
+
+    #[test]
+    fn scheduler_run_limits_events_per_block() {
+        new_test_ext().execute_with(|| {
+            // The Scheduler starts on the day the contracts are deployed.
+            <timestamp::Module<Test>>::set_timestamp(1_420_070_400);
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            assert!(Contracts::set_max_events_per_block(0).is_err());
+            assert_ok!(Contracts::set_max_events_per_block(1));
+
+            // Deploying two contracts with their first event on the same day.
+            let mut ids = Vec::new();
+            for _ in 0..2 {
+                let id = H256::random();
+                let mut terms = Terms::new(id);
+                terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+                terms.contract_role = Some(ContractRole::RPA);
+                terms.contract_type = Some(ContractType::PAM);
+                terms.counterparty_id = Some(H256::random());
+                terms.creator_id = Some(H256::random());
+                terms.currency = Some(1);
+                terms.settlement_currency = Some(1);
+                terms.day_count_convention = Some(DayCountConvention::_30E360);
+                terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+                terms.maturity_date = Time::from_values(2015, 04, 02, 00, 00, 00);
+                terms.nominal_interest_rate = Real::from(0);
+                terms.notional_principal = Real::from(1000);
+                terms.rate_spread = Real::from(0);
+                assert_ok!(<assets::Module<Test>>::mint(
+                    terms.creator_id.unwrap(),
+                    terms.currency.unwrap(),
+                    terms.notional_principal,
+                ));
+
+                let contract = Contracts::deploy_pam(t0, terms).unwrap();
+                <Contracts as Store>::Contracts::insert(id, contract.clone());
+                Contracts::scheduler_push(ScheduledEvent {
+                    time: contract.schedule[0].time,
+                    contract_id: id,
+                    index: 0,
+                });
+                ids.push(id);
+            }
+
+            // Only one event is executed per block, the other one is left for the next block.
+            let now = Time::from_values(2015, 01, 02, 00, 00, 05);
+            let day = scheduler_day(now);
+            assert_ok!(Contracts::scheduler_run(now));
+            let status_dates: Vec<Time> = ids
+                .iter()
+                .map(|id| <Contracts as Store>::Contracts::get(id).states.status_date)
+                .collect();
+            assert!(status_dates.contains(&t0));
+            assert!(status_dates.contains(&Time::from_values(2015, 01, 02, 00, 00, 00)));
+            assert_eq!(<Contracts as Store>::SchedulerBacklog::get(), 1);
+            assert_eq!(Contracts::scheduler_bucket(day).len(), 1);
+            // The executed slot isn't read again.
+            assert_eq!(<Contracts as Store>::SchedulerCursor::get(day), 1);
+            assert_eq!(<Contracts as Store>::SchedulerPending::get(day), 1);
+
+            assert_ok!(Contracts::scheduler_run(now));
+            for id in ids.iter() {
+                assert_eq!(
+                    <Contracts as Store>::Contracts::get(id).states.status_date,
+                    Time::from_values(2015, 01, 02, 00, 00, 00)
+                );
+            }
+            assert_eq!(<Contracts as Store>::SchedulerBacklog::get(), 0);
+            assert!(Contracts::scheduler_bucket(day).is_empty());
+            assert_eq!(<Contracts as Store>::SchedulerCursor::get(day), 2);
+            assert_eq!(<Contracts as Store>::SchedulerPending::get(day), 0);
+        });
+    }
+
+    #[test]
+    fn scheduler_run_keeps_events_later_in_the_day() {
+        new_test_ext().execute_with(|| {
+            <timestamp::Module<Test>>::set_timestamp(1_420_070_400);
+            let day = scheduler_day(Time::from_values(2015, 01, 02, 00, 00, 00));
+            let ids: Vec<H256> = (0..3).map(|_| H256::random()).collect();
+
+            // The first event is due in the evening, the other ones at midnight.
+            for (i, id) in ids.iter().enumerate() {
+                let hour = if i == 0 { 18 } else { 0 };
+                Contracts::scheduler_push(ScheduledEvent {
+                    time: Time::from_values(2015, 01, 02, hour, 00, 00),
+                    contract_id: *id,
+                    index: 0,
+                });
+            }
+            assert_eq!(<Contracts as Store>::SchedulerPending::get(day), 3);
+
+            // The events due at midnight are executed (the contracts don't exist, so they have
+            // no events left), the cursor stays at the event due in the evening.
+            assert_ok!(Contracts::scheduler_run(Time::from_values(
+                2015, 01, 02, 00, 00, 05
+            )));
+            assert_eq!(Contracts::scheduler_bucket(day).len(), 1);
+            assert_eq!(Contracts::scheduler_bucket(day)[0].contract_id, ids[0]);
+            assert_eq!(<Contracts as Store>::SchedulerCursor::get(day), 0);
+            assert_eq!(<Contracts as Store>::SchedulerPending::get(day), 1);
+            assert_eq!(<Contracts as Store>::SchedulerBacklog::get(), 0);
+
+            // Once the day is over, its bucket is removed.
+            assert_ok!(Contracts::scheduler_run(Time::from_values(
+                2015, 01, 03, 00, 00, 00
+            )));
+            assert!(Contracts::scheduler_bucket(day).is_empty());
+            assert!(!<Contracts as Store>::SchedulerCursor::exists(day));
+            assert!(!<Contracts as Store>::SchedulerPending::exists(day));
+        });
+    }
+
+    #[test]
+    fn scheduler_run_isolates_failures() {
+        new_test_ext().execute_with(|| {
+            // The Scheduler starts on the day the contracts are deployed.
+            <timestamp::Module<Test>>::set_timestamp(1_420_070_400);
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+
+            // Deploying two contracts with their first event on the same day.
+            let mut ids = Vec::new();
+            for _ in 0..2 {
+                let id = H256::random();
+                let mut terms = Terms::new(id);
+                terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+                terms.contract_role = Some(ContractRole::RPA);
+                terms.contract_type = Some(ContractType::PAM);
+                terms.counterparty_id = Some(H256::random());
+                terms.creator_id = Some(H256::random());
+                terms.currency = Some(1);
+                terms.settlement_currency = Some(1);
+                terms.day_count_convention = Some(DayCountConvention::_30E360);
+                terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+                terms.maturity_date = Time::from_values(2015, 04, 02, 00, 00, 00);
+                terms.nominal_interest_rate = Real::from(0);
+                terms.notional_principal = Real::from(1000);
+                terms.rate_spread = Real::from(0);
+                assert_ok!(<assets::Module<Test>>::mint(
+                    terms.creator_id.unwrap(),
+                    terms.currency.unwrap(),
+                    terms.notional_principal,
+                ));
+
+                let contract = Contracts::deploy_pam(t0, terms).unwrap();
+                <Contracts as Store>::Contracts::insert(id, contract.clone());
+                Contracts::scheduler_push(ScheduledEvent {
+                    time: contract.schedule[0].time,
+                    contract_id: id,
+                    index: 0,
+                });
+                ids.push(id);
+            }
+
+            // Breaking the first contract, its first event can't be applied to a PAM.
+            <Contracts as Store>::Contracts::mutate(ids[0], |contract| {
+                contract.schedule[0].event_type = EventType::XD
+            });
+
+            // The failure of the first contract doesn't stop the second one.
+            let mut now = Time::from_values(2015, 01, 02, 00, 00, 05);
+            assert_ok!(Contracts::scheduler_run(now));
+            assert_eq!(
+                <Contracts as Store>::Contracts::get(ids[0])
+                    .states
+                    .status_date,
+                t0
+            );
+            assert_eq!(
+                <Contracts as Store>::Contracts::get(ids[1])
+                    .states
+                    .status_date,
+                Time::from_values(2015, 01, 02, 00, 00, 00)
+            );
+            let failure = <Contracts as Store>::SchedulerFailures::get(ids[0]);
+            assert_eq!(failure.attempts, 1);
+            assert_eq!(failure.scheduled_event.index, 0);
+            assert!(!<Contracts as Store>::SchedulerFailures::exists(ids[1]));
+
+            // The first contract is retried the next day.
+            let retry = Contracts::scheduler_bucket(scheduler_day(now) + 1);
+            assert_eq!(retry.len(), 1);
+            assert_eq!(retry[0].contract_id, ids[0]);
+            assert_eq!(retry[0].index, 0);
+            assert!(Contracts::scheduler_resume(now, ids[0]).is_err());
+
+            // After the last attempt the contract is halted.
+            for _ in 1..MAX_SCHEDULER_ATTEMPTS {
+                now = now.add_days(16);
+                assert_ok!(Contracts::scheduler_run(now));
+            }
+            let failure = <Contracts as Store>::SchedulerFailures::get(ids[0]);
+            assert_eq!(failure.attempts, MAX_SCHEDULER_ATTEMPTS);
+            assert!(failure.is_halted());
+            now = now.add_days(16);
+            assert_ok!(Contracts::scheduler_run(now));
+            assert_eq!(
+                <Contracts as Store>::SchedulerFailures::get(ids[0]).attempts,
+                MAX_SCHEDULER_ATTEMPTS
+            );
+
+            // Once repaired, the contract can be resumed.
+            <Contracts as Store>::Contracts::mutate(ids[0], |contract| {
+                contract.schedule[0].event_type = EventType::IED
+            });
+            assert_ok!(Contracts::scheduler_resume(now, ids[0]));
+            assert_ok!(Contracts::scheduler_run(now));
+            assert!(!<Contracts as Store>::SchedulerFailures::exists(ids[0]));
+            assert_ne!(
+                <Contracts as Store>::Contracts::get(ids[0])
+                    .states
+                    .status_date,
+                t0
+            );
+        });
+    }
+
+    #[test]
+    fn scheduler_run_extends_open_ended_contracts() {
+        new_test_ext().execute_with(|| {
+            // The Scheduler starts on the day the contract is deployed.
+            <timestamp::Module<Test>>::set_timestamp(1_420_070_400);
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::CLM);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.settlement_currency = Some(1);
+            terms.day_count_convention = Some(DayCountConvention::_30E360);
+            terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+            terms.nominal_interest_rate = Real::from(0);
+            terms.notional_principal = Real::from(1000);
+            terms.cycle_anchor_date_of_interest_payment =
+                Time::from_values(2015, 02, 02, 00, 00, 00);
+            terms.cycle_of_interest_payment = Some(Cycle::Months(1, true));
+
+            <assets::Module<Test>>::mint(
+                terms.creator_id.unwrap(),
+                terms.currency.unwrap(),
+                terms.notional_principal,
+            );
+
+            let contract = Contracts::deploy_clm(t0, terms).unwrap();
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+            let event = ScheduledEvent {
+                time: contract.schedule[0].time,
+                contract_id: id,
+                index: 0,
+            };
+            Contracts::scheduler_push(event);
+
+            // The first piece of the schedule ends on 2016-01-02, so running the Scheduler after
+            // it makes it create the next piece.
+            assert_ok!(Contracts::scheduler_run(Time::from_values(
+                2016, 03, 01, 00, 00, 00
+            )));
+            let contract = <Contracts as Store>::Contracts::get(id);
+            assert_eq!(contract.schedule.len(), 25);
+            assert_eq!(
+                contract.states.status_date,
+                Time::from_values(2016, 02, 02, 00, 00, 00)
+            );
+            let time = Time::from_values(2016, 03, 02, 00, 00, 00);
+            let event = Contracts::scheduler_bucket(scheduler_day(time))[0];
+            assert_eq!(event.time, time);
+            assert_eq!(event.index, 14);
+
+            // After the termination of the contract its remaining events are dropped.
+            assert_ok!(Contracts::progress(
+                ContractEvent::new(Time::from_values(2016, 03, 01, 00, 00, 00), EventType::MD),
+                id
+            ));
+            assert_ok!(Contracts::scheduler_run(Time::from_values(
+                2016, 04, 01, 00, 00, 00
+            )));
+            assert!(Contracts::scheduler_bucket(scheduler_day(time)).is_empty());
+        });
+    }
+
+    #[test]
+    fn scheduler_run_replays_rate_resets_with_past_oracle_values() {
+        new_test_ext().execute_with(|| {
+            <timestamp::Module<Test>>::set_timestamp(1_420_070_400);
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::PAM);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.settlement_currency = Some(1);
+            terms.day_count_convention = Some(DayCountConvention::_30E360);
+            terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+            terms.maturity_date = Time::from_values(2015, 04, 02, 00, 00, 00);
+            terms.nominal_interest_rate = Real::from(4) / Real::from(100);
+            terms.notional_principal = Real::from(1000);
+            terms.cycle_of_rate_reset = Some(Cycle::Months(1, true));
+            terms.market_object_code_rate_reset = Some(H256::random());
+            terms.rate_spread = Real::from(0);
+            terms.rate_multiplier = Real::from(1);
+            terms.period_floor = Real::from(0);
+            terms.period_cap = Real::from(1);
+            terms.life_floor = Real::from(0);
+            terms.life_cap = Real::from(1);
+            assert_ok!(<assets::Module<Test>>::mint(
+                terms.creator_id.unwrap(),
+                terms.currency.unwrap(),
+                terms.notional_principal,
+            ));
+            let oracle_id = terms.market_object_code_rate_reset.unwrap();
+            assert_ok!(<oracle::Module<Test>>::set(
+                oracle_id,
+                Real::from(5) / Real::from(100)
+            ));
+
+            let contract = Contracts::deploy_pam(t0, terms).unwrap();
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+            Contracts::scheduler_push(ScheduledEvent {
+                time: contract.schedule[0].time,
+                contract_id: id,
+                index: 0,
+            });
+
+            // The oracle is updated after the first rate reset (on 2015-02-02) but before the
+            // Scheduler catches up with it.
+            <timestamp::Module<Test>>::set_timestamp(1_424_000_000);
+            assert_ok!(<oracle::Module<Test>>::set(
+                oracle_id,
+                Real::from(9) / Real::from(100)
+            ));
+            assert_ok!(Contracts::scheduler_run(Time::from_unix(1_424_000_000)));
+
+            // The rate reset used the value the oracle had on its date.
+            let contract = <Contracts as Store>::Contracts::get(id);
+            assert_eq!(
+                contract.states.status_date,
+                Time::from_values(2015, 02, 02, 00, 00, 00)
+            );
+            assert_eq!(
+                contract.states.nominal_interest_rate,
+                Real::from(5) / Real::from(100)
+            );
+
+            // A rate reset can't be executed without a value of the oracle.
+            let mut contract = contract;
+            contract.terms.market_object_code_rate_reset = Some(H256::random());
+            let event =
+                ContractEvent::new(Time::from_values(2015, 03, 02, 00, 00, 00), EventType::RR);
+            assert!(Contracts::progress_payoff(event, contract).is_err());
         });
     }
 }
@@ -12,18 +12,24 @@
 // GNU General Public License for more details.
 
 use super::*;
+use core::cmp::Ordering;
 
-/// The contract event struct, it is composed of a time and an event type. It can be ordered, first
-/// by time (from earliest to latest) and secondly by priority of event type (from highest to lowest).
+/// The contract event struct, it is composed of a time, an event type and an amount. It can be
+/// ordered, first by time (from earliest to latest) and secondly by priority of event type (from
+/// highest to lowest).
 #[derive(Clone, Copy, Decode, Debug, Encode, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Event {
+pub struct ContractEvent {
     pub time: Time,
     pub event_type: EventType,
+    /// The amount of an unscheduled event, like a principal drawdown initiated by a user. It is
+    /// `Real(None)` for all the events created by the schedule.
+    pub amount: Real,
 }
 
-/// All ACTUS contract event types as specifed in the ACTUS paper. They are ordered from highest to
-/// lowest priority.
-#[derive(Clone, Copy, Decode, Debug, Encode, PartialEq, Eq, PartialOrd, Ord)]
+/// All ACTUS contract event types as specifed in the ACTUS paper. New event types are added at the
+/// end, so that the encoding of the existing ones doesn't change, their priority is given by
+/// `priority`.
+#[derive(Clone, Copy, Decode, Debug, Encode, PartialEq, Eq)]
 pub enum EventType {
     IED,
     FP,
@@ -47,14 +53,73 @@ pub enum EventType {
     XD,
     STD,
     // AD, // This event is for analysis only, it has no effect on the state.
+    PI,
+    IPFX,
+    IPFL,
+}
+
+impl EventType {
+    /// Returns the priority of the event type, events happening at the same time are executed
+    /// from the highest priority (0) to the lowest.
+    pub fn priority(&self) -> u8 {
+        match self {
+            EventType::IED => 0,
+            EventType::FP => 1,
+            EventType::PR => 2,
+            EventType::PI => 3,
+            EventType::PD => 4,
+            EventType::PRF => 5,
+            EventType::PY => 6,
+            EventType::PP => 7,
+            EventType::IP => 8,
+            EventType::IPFX => 9,
+            EventType::IPFL => 10,
+            EventType::IPCI => 11,
+            EventType::CE => 12,
+            EventType::RRF => 13,
+            EventType::RR => 14,
+            EventType::DV => 15,
+            EventType::PRD => 16,
+            EventType::MR => 17,
+            EventType::TD => 18,
+            EventType::SC => 19,
+            EventType::IPCB => 20,
+            EventType::MD => 21,
+            EventType::XD => 22,
+            EventType::STD => 23,
+        }
+    }
+}
+
+// The event types are ordered from highest to lowest priority.
+impl PartialOrd for EventType {
+    fn partial_cmp(&self, other: &EventType) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EventType {
+    fn cmp(&self, other: &EventType) -> Ordering {
+        self.priority().cmp(&other.priority())
+    }
 }
 
-impl Event {
-    /// A constructor for the Event type. It is just syntactic sugar.
-    pub fn new(time: Time, event_type: EventType) -> Event {
-        Event {
+impl ContractEvent {
+    /// A constructor for the ContractEvent type. It is just syntactic sugar.
+    pub fn new(time: Time, event_type: EventType) -> ContractEvent {
+        ContractEvent {
+            time: time,
+            event_type: event_type,
+            amount: Real(None),
+        }
+    }
+
+    /// A constructor for events that carry an amount (e.g. unscheduled principal drawdowns).
+    pub fn with_amount(time: Time, event_type: EventType, amount: Real) -> ContractEvent {
+        ContractEvent {
             time: time,
             event_type: event_type,
+            amount: amount,
         }
     }
 }
@@ -70,10 +135,10 @@ mod tests {
         let e1 = EventType::PR;
         let e2 = EventType::CE;
 
-        let x1 = Event::new(t1, e1);
-        let x2 = Event::new(t1, e2);
-        let x3 = Event::new(t2, e1);
-        let x4 = Event::new(t2, e2);
+        let x1 = ContractEvent::new(t1, e1);
+        let x2 = ContractEvent::new(t1, e2);
+        let x3 = ContractEvent::new(t2, e1);
+        let x4 = ContractEvent::new(t2, e2);
 
         let mut v1 = vec![x4, x2, x1, x3];
         v1.sort_unstable();
@@ -81,4 +146,19 @@ mod tests {
 
         assert_eq!(v1, v2);
     }
+
+    #[test]
+    fn keeps_the_encoding_and_priority() {
+        // The event types added later are encoded after the original ones.
+        assert_eq!(EventType::STD.encode(), vec![20]);
+        assert_eq!(EventType::PI.encode(), vec![21]);
+        assert_eq!(EventType::IPFX.encode(), vec![22]);
+        assert_eq!(EventType::IPFL.encode(), vec![23]);
+
+        // But they are still executed in the order of priority.
+        assert!(EventType::PR < EventType::PI);
+        assert!(EventType::PI < EventType::PD);
+        assert!(EventType::IPFL < EventType::IPCI);
+        assert!(EventType::MD < EventType::STD);
+    }
 }
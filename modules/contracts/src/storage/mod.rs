@@ -26,5 +26,23 @@ pub use terms::*;
 pub struct Contract {
     pub terms: Terms,
     pub states: States,
-    pub schedule: Vec<Event>,
+    pub schedule: Vec<ContractEvent>,
+}
+
+// This struct records an event executed by a contract. The time of the event is the time it was
+// scheduled for, which can be earlier than the block in which it was executed (e.g. after a halt
+// of the chain).
+#[derive(Clone, Decode, Debug, Encode, PartialEq)]
+pub struct ExecutedEvent {
+    pub event: ContractEvent,
+    pub block_number: u64,
+    pub payoffs: Vec<(Option<u32>, Real)>,
+}
+
+// This struct contains the terms of a contract proposed by its creator, which are deployed once
+// the counterparty accepts them before the expiry.
+#[derive(Clone, Decode, Debug, Encode, Default, PartialEq)]
+pub struct Proposal {
+    pub terms: Terms,
+    pub expiry: Time,
 }
@@ -162,7 +162,7 @@ pub enum ClearingHouse {
     N,
 }
 
-#[derive(Clone, Copy, Decode, Debug, Encode, PartialEq)]
+#[derive(Clone, Copy, Decode, Debug, Encode, PartialEq, Eq)]
 pub enum ContractPerformance {
     PF,
     DL,
@@ -203,7 +203,7 @@ pub enum ContractRole {
     OBL,
 }
 
-#[derive(Clone, Copy, Decode, Debug, Encode, PartialEq)]
+#[derive(Clone, Copy, Decode, Debug, Encode, PartialEq, Eq)]
 pub enum ContractType {
     PAM,
     ANN,
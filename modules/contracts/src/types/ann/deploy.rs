@@ -258,10 +258,10 @@ impl<T: Trait> Module<T> {
         }
 
         // Creating the schedule for all the events.
-        let mut schedule: Vec<Event> = Vec::new();
+        let mut schedule: Vec<ContractEvent> = Vec::new();
 
         // Inital exchange date event
-        let event = Event::new(terms.initial_exchange_date, EventType::IED);
+        let event = ContractEvent::new(terms.initial_exchange_date, EventType::IED);
         schedule.push(event);
 
         // Principal Redemption event
@@ -287,14 +287,15 @@ impl<T: Trait> Module<T> {
             terms.end_of_month_convention,
         )?;
 
-        // Note: The last entry in vec is supposed to not enter the schedule.
-        for i in 0..vec.len() - 2 {
-            let event = Event::new(vec[i], EventType::PR);
+        // Note: The last entry in vec is the maturity date, it is supposed to not enter the
+        // schedule since the MD event redeems the remaining principal.
+        for pair in vec.windows(2) {
+            let event = ContractEvent::new(pair[0], EventType::PR);
             schedule.push(event);
         }
 
         // Maturity date event
-        let event = Event::new(terms.maturity_date, EventType::MD);
+        let event = ContractEvent::new(terms.maturity_date, EventType::MD);
         schedule.push(event);
 
         // Principal prepayment event
@@ -323,7 +324,7 @@ impl<T: Trait> Module<T> {
             )?;
 
             for t in vec {
-                let event = Event::new(t, EventType::PP);
+                let event = ContractEvent::new(t, EventType::PP);
                 schedule.push(event);
             }
         }
@@ -333,7 +334,7 @@ impl<T: Trait> Module<T> {
         } else {
             for e in schedule.clone() {
                 if e.event_type == EventType::PP {
-                    let event = Event::new(e.time, EventType::PY);
+                    let event = ContractEvent::new(e.time, EventType::PY);
                     schedule.push(event);
                 }
             }
@@ -363,17 +364,17 @@ impl<T: Trait> Module<T> {
             )?;
 
             for t in vec {
-                let event = Event::new(t, EventType::FP);
+                let event = ContractEvent::new(t, EventType::FP);
                 schedule.push(event);
             }
         }
 
         // Purchase date event
-        let event = Event::new(terms.purchase_date, EventType::PRD);
+        let event = ContractEvent::new(terms.purchase_date, EventType::PRD);
         schedule.push(event);
 
         // Termination date event
-        let event = Event::new(terms.termination_date, EventType::TD);
+        let event = ContractEvent::new(terms.termination_date, EventType::TD);
         schedule.push(event);
 
         // Interest payment event
@@ -428,7 +429,7 @@ impl<T: Trait> Module<T> {
                 {
                     break;
                 }
-                let event = Event::new(t, EventType::IP);
+                let event = ContractEvent::new(t, EventType::IP);
                 schedule.push(event);
             }
         }
@@ -441,7 +442,7 @@ impl<T: Trait> Module<T> {
         )?;
 
         for t in vec {
-            let event = Event::new(t, EventType::IP);
+            let event = ContractEvent::new(t, EventType::IP);
             schedule.push(event);
         }
 
@@ -471,7 +472,7 @@ impl<T: Trait> Module<T> {
             )?;
 
             for t in vec {
-                let event = Event::new(t, EventType::IPCI);
+                let event = ContractEvent::new(t, EventType::IPCI);
                 schedule.push(event);
             }
         }
@@ -502,7 +503,7 @@ impl<T: Trait> Module<T> {
             )?;
 
             for t in vec {
-                let event = Event::new(t, EventType::IPCB);
+                let event = ContractEvent::new(t, EventType::IPCB);
                 schedule.push(event);
             }
         }
@@ -539,20 +540,22 @@ impl<T: Trait> Module<T> {
                 }
                 for t in vec {
                     if t != t_rry {
-                        let event = Event::new(t, EventType::RR);
+                        let event = ContractEvent::new(t, EventType::RR);
                         schedule.push(event);
                     }
                 }
             } else {
                 for t in vec {
-                    let event = Event::new(t, EventType::RR);
+                    let event = ContractEvent::new(t, EventType::RR);
                     schedule.push(event);
                 }
             }
         }
 
         // Rate reset fixed event
-        if terms.cycle_anchor_date_of_rate_reset == Time(None) && terms.cycle_of_rate_reset == None
+        if (terms.cycle_anchor_date_of_rate_reset == Time(None)
+            && terms.cycle_of_rate_reset == None)
+            || terms.next_reset_rate == Real(None)
         {
         } else {
             let mut s: Time = Time(None);
@@ -575,7 +578,7 @@ impl<T: Trait> Module<T> {
 
             for t in vec {
                 if t > terms.status_date {
-                    let event = Event::new(t, EventType::RRF);
+                    let event = ContractEvent::new(t, EventType::RRF);
                     schedule.push(event);
                     break;
                 }
@@ -608,7 +611,7 @@ impl<T: Trait> Module<T> {
             )?;
 
             for t in vec {
-                let event = Event::new(t, EventType::SC);
+                let event = ContractEvent::new(t, EventType::SC);
                 schedule.push(event);
             }
         }
@@ -672,6 +675,44 @@ impl<T: Trait> Module<T> {
                     * states.nominal_interest_rate;
         }
 
+        // Interest Calculation Base variable
+        if terms.initial_exchange_date > t0 {
+            states.interest_calculation_base = Real::from(0);
+        } else if terms.interest_calculation_base == Some(InterestCalculationBase::NTIED) {
+            states.interest_calculation_base = utilities::contract_role_sign(terms.contract_role)
+                * terms.interest_calculation_base_amount;
+        } else {
+            states.interest_calculation_base = states.notional_principal;
+        }
+
+        // Next Principal Redemption Payment variable
+        if terms.next_principal_redemption_payment != Real(None) {
+            states.next_principal_redemption_payment =
+                utilities::contract_role_sign(terms.contract_role)
+                    * terms.next_principal_redemption_payment;
+        } else if terms.initial_exchange_date > t0 {
+            // It will be calculated by the initial exchange event.
+            states.next_principal_redemption_payment = Real::from(0);
+        } else {
+            let mut vec: Vec<Time> = Vec::new();
+            for e in schedule.clone() {
+                if (e.event_type == EventType::PR || e.event_type == EventType::MD) && e.time > t0 {
+                    vec.push(e.time);
+                }
+            }
+            if vec.is_empty() {
+                states.next_principal_redemption_payment = Real::from(0);
+            } else {
+                states.next_principal_redemption_payment = utilities::annuity_amount(
+                    vec,
+                    terms.day_count_convention.unwrap(),
+                    states.notional_principal,
+                    states.accrued_interest,
+                    states.nominal_interest_rate,
+                );
+            }
+        }
+
         // Fee Accrued variable
         if terms.fee_rate == Real(None) {
             states.fee_accrued = Real::from(0);
@@ -800,9 +841,15 @@ mod tests {
         type OnTimestampSet = ();
         type MinimumPeriod = MinimumPeriod;
     }
-    impl oracle::Trait for Test {}
-    impl assets::Trait for Test {}
-    impl Trait for Test {}
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
     type Contracts = Module<Test>;
 
     // This function basically just builds a genesis storage key/value store according to
@@ -817,7 +864,47 @@ mod tests {
     #[test]
     fn deploy_ann_works() {
         new_test_ext().execute_with(|| {
-            // TODO: Implement test cases
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::ANN);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.day_count_convention = Some(DayCountConvention::_30E360);
+            terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+            terms.maturity_date = Time::from_values(2015, 04, 02, 00, 00, 00);
+            terms.nominal_interest_rate = Real::from(0);
+            terms.notional_principal = Real::from(1000);
+            terms.cycle_anchor_date_of_principal_redemption =
+                Time::from_values(2015, 02, 02, 00, 00, 00);
+            terms.cycle_of_principal_redemption = Some(Cycle::Months(1, true));
+            terms.rate_spread = Real::from(0);
+            terms.scaling_effect = None;
+
+            let contract = Contracts::deploy_ann(t0, terms).unwrap();
+
+            // Every redemption date before the maturity date has a PR event, the principal left
+            // at the maturity date is redeemed by the MD event.
+            let redemptions: Vec<Time> = contract
+                .schedule
+                .iter()
+                .filter(|event| event.event_type == EventType::PR)
+                .map(|event| event.time)
+                .collect();
+            assert_eq!(
+                redemptions,
+                vec![
+                    Time::from_values(2015, 02, 02, 00, 00, 00),
+                    Time::from_values(2015, 03, 02, 00, 00, 00)
+                ]
+            );
+            let last_event = contract.schedule[contract.schedule.len() - 1];
+            assert_eq!(last_event.event_type, EventType::MD);
+            assert_eq!(last_event.time, Time::from_values(2015, 04, 02, 00, 00, 00));
         });
     }
 }
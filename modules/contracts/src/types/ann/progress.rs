@@ -15,13 +15,81 @@ use super::*;
 
 // TODO: Add support for user-initiated events.
 impl<T: Trait> Module<T> {
-    pub fn progress_ann(event: Event, mut contract: Contract) -> ContractResult<(Real, Contract)> {
+    pub fn progress_ann(
+        event: ContractEvent,
+        mut contract: Contract,
+    ) -> ContractResult<(Real, Contract)> {
         // Getting t0 from the status_date attribute since they are equal.
         // (And status_date is not supposed to change)
         let t0 = contract.terms.status_date;
 
-        // TODO: Remove this and update contract state and calculate payoff using matched events
-        Ok((Real::from(0), contract))
+        match event.event_type {
+            EventType::IED => Ok((
+                functions::pof_ied_ann(event, &contract),
+                functions::stf_ied_ann(event, &t0, contract),
+            )),
+            EventType::PR => Ok((
+                functions::pof_pr_ann(event, &contract),
+                functions::stf_pr_ann(event, &t0, contract),
+            )),
+            EventType::PRF => Ok((
+                functions::pof_prf_ann(event, &contract),
+                functions::stf_prf_ann(event, &t0, contract),
+            )),
+            EventType::MD => Ok((
+                functions::pof_md_ann(event, &contract),
+                functions::stf_md_ann(event, &t0, contract),
+            )),
+            EventType::PP => Ok((
+                functions::pof_pp_ann(event, &contract),
+                functions::stf_pp_ann(event, &t0, contract),
+            )),
+            EventType::PY => Ok((
+                Self::pof_py_ann(event, &contract),
+                functions::stf_py_ann(event, &t0, contract),
+            )),
+            EventType::FP => Ok((
+                functions::pof_fp_ann(event, &contract),
+                functions::stf_fp_ann(event, &t0, contract),
+            )),
+            EventType::PRD => Ok((
+                functions::pof_prd_ann(event, &contract),
+                functions::stf_prd_ann(event, &t0, contract),
+            )),
+            EventType::TD => Ok((
+                functions::pof_td_ann(event, &contract),
+                functions::stf_td_ann(event, &t0, contract),
+            )),
+            EventType::IP => Ok((
+                functions::pof_ip_ann(event, &contract),
+                functions::stf_ip_ann(event, &t0, contract),
+            )),
+            EventType::IPCI => Ok((
+                functions::pof_ipci_ann(event, &contract),
+                functions::stf_ipci_ann(event, &t0, contract),
+            )),
+            EventType::IPCB => Ok((
+                functions::pof_ipcb_ann(event, &contract),
+                functions::stf_ipcb_ann(event, &t0, contract),
+            )),
+            EventType::RR => Ok((
+                functions::pof_rr_ann(event, &contract),
+                Self::stf_rr_ann(event, &t0, contract),
+            )),
+            EventType::RRF => Ok((
+                functions::pof_rrf_ann(event, &contract),
+                functions::stf_rrf_ann(event, &t0, contract),
+            )),
+            EventType::SC => Ok((
+                functions::pof_sc_ann(event, &contract),
+                Self::stf_sc_ann(event, &t0, contract),
+            )),
+            EventType::CE => Ok((
+                functions::pof_ce_ann(event, &contract),
+                functions::stf_ce_ann(event, &t0, contract),
+            )),
+            _ => Err("Event not applicable"),
+        }
     }
 }
 
@@ -81,9 +149,15 @@ mod tests {
         type OnTimestampSet = ();
         type MinimumPeriod = MinimumPeriod;
     }
-    impl oracle::Trait for Test {}
-    impl assets::Trait for Test {}
-    impl Trait for Test {}
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
     type Contracts = Module<Test>;
 
     // This function basically just builds a genesis storage key/value store according to
@@ -98,7 +172,162 @@ mod tests {
     #[test]
     fn progress_ann_works() {
         new_test_ext().execute_with(|| {
-            // TODO: Test
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::ANN);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.day_count_convention = Some(DayCountConvention::_30E360);
+            terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+            terms.maturity_date = Time::from_values(2015, 04, 02, 00, 00, 00);
+            terms.nominal_interest_rate = Real::from(0);
+            terms.notional_principal = Real::from(1000);
+            terms.cycle_anchor_date_of_principal_redemption =
+                Time::from_values(2015, 02, 02, 00, 00, 00);
+            terms.cycle_of_principal_redemption = Some(Cycle::Months(1, true));
+            terms.cycle_of_rate_reset = Some(Cycle::Months(1, true));
+            terms.market_object_code_rate_reset = Some(H256::random());
+            terms.rate_spread = Real::from(0);
+            terms.scaling_effect = None;
+
+            let mut contract = Contracts::deploy_ann(t0, terms).unwrap();
+
+            assert_eq!(
+                contract.schedule[0],
+                ContractEvent::new(Time::from_values(2015, 01, 02, 00, 00, 00), EventType::IED)
+            );
+            contract = Contracts::progress_ann(contract.schedule[0], contract)
+                .unwrap()
+                .1;
+            assert_eq!(contract.states.notional_principal, Real::from(1000));
+            assert_eq!(contract.states.accrued_interest, Real::from(0));
+            // With a zero interest rate the annuity is split evenly among the three
+            // remaining redemption dates (two PR events and the MD event).
+            assert_eq!(
+                contract.states.next_principal_redemption_payment,
+                Real(Some(333333333333))
+            );
+
+            assert_eq!(
+                contract.schedule[1],
+                ContractEvent::new(Time::from_values(2015, 02, 02, 00, 00, 00), EventType::PR)
+            );
+            let result = Contracts::progress_ann(contract.schedule[1], contract).unwrap();
+            assert_eq!(result.0, Real(Some(333333333333)));
+            contract = result.1;
+            assert_eq!(contract.states.notional_principal, Real(Some(666666666667)));
+            assert_eq!(
+                contract.states.interest_calculation_base,
+                Real(Some(666666666667))
+            );
+
+            assert_eq!(
+                contract.schedule[2],
+                ContractEvent::new(Time::from_values(2015, 02, 02, 00, 00, 00), EventType::IP)
+            );
+            let result = Contracts::progress_ann(contract.schedule[2], contract).unwrap();
+            assert_eq!(result.0, Real::from(0));
+            assert_eq!(result.1.states.accrued_interest, Real::from(0));
+        });
+    }
+
+    #[test]
+    fn progress_ann_prepayment_works() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::ANN);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.day_count_convention = Some(DayCountConvention::_30E360);
+            terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+            terms.maturity_date = Time::from_values(2015, 04, 02, 00, 00, 00);
+            terms.nominal_interest_rate = Real::from(0);
+            terms.notional_principal = Real::from(900);
+            terms.cycle_anchor_date_of_principal_redemption =
+                Time::from_values(2015, 02, 02, 00, 00, 00);
+            terms.cycle_of_principal_redemption = Some(Cycle::Months(1, true));
+            terms.prepayment_effect = Some(PrepaymentEffect::A);
+            terms.rate_spread = Real::from(0);
+            terms.scaling_effect = None;
+
+            let mut contract = Contracts::deploy_ann(t0, terms).unwrap();
+            contract = Contracts::progress_ann(contract.schedule[0], contract)
+                .unwrap()
+                .1;
+            assert_eq!(
+                contract.states.next_principal_redemption_payment,
+                Real::from(300)
+            );
+
+            // The debtor prepays part of the principal, the annuity is reduced accordingly.
+            let event = ContractEvent::with_amount(
+                Time::from_values(2015, 01, 15, 00, 00, 00),
+                EventType::PP,
+                Real::from(300),
+            );
+            let result = Contracts::progress_ann(event, contract).unwrap();
+            assert_eq!(result.0, Real::from(300));
+            contract = result.1;
+            assert_eq!(contract.states.notional_principal, Real::from(600));
+            assert_eq!(
+                contract.states.next_principal_redemption_payment,
+                Real::from(200)
+            );
+        });
+    }
+
+    #[test]
+    fn progress_ann_redeems_nothing_if_interest_exceeds_the_instalment() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::ANN);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.day_count_convention = Some(DayCountConvention::_30E360);
+            terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+            terms.maturity_date = Time::from_values(2015, 04, 02, 00, 00, 00);
+            terms.nominal_interest_rate = Real::from(12) / Real::from(100);
+            terms.notional_principal = Real::from(1000);
+            terms.next_principal_redemption_payment = Real::from(5);
+            terms.cycle_anchor_date_of_principal_redemption =
+                Time::from_values(2015, 02, 02, 00, 00, 00);
+            terms.cycle_of_principal_redemption = Some(Cycle::Months(1, true));
+            terms.rate_spread = Real::from(0);
+            terms.scaling_effect = None;
+
+            let mut contract = Contracts::deploy_ann(t0, terms).unwrap();
+            contract = Contracts::progress_ann(contract.schedule[0], contract)
+                .unwrap()
+                .1;
+            assert_eq!(
+                contract.states.next_principal_redemption_payment,
+                Real::from(5)
+            );
+
+            // The interest of the first month (about 10) exceeds the instalment (5), so neither party
+            // pays principal.
+            assert_eq!(contract.schedule[1].event_type, EventType::PR);
+            let result = Contracts::progress_ann(contract.schedule[1], contract).unwrap();
+            assert_eq!(result.0, Real::from(0));
+            assert_eq!(result.1.states.notional_principal, Real::from(1000));
+            assert!(result.1.states.accrued_interest > Real::from(5));
         });
     }
 }
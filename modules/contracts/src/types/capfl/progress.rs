@@ -0,0 +1,193 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    pub fn progress_capfl(
+        event: ContractEvent,
+        contract: Contract,
+    ) -> ContractResult<(Real, Contract)> {
+        // Getting t0 from the status_date attribute since they are equal.
+        // (And status_date is not supposed to change)
+        let t0 = contract.terms.status_date;
+
+        // CAPFL has its own functions for the rate resets, the remaining ones are shared with
+        // OPTNS.
+        match event.event_type {
+            EventType::PRD => Ok((
+                functions::pof_prd_optns(event, &contract),
+                functions::stf_prd_optns(event, &t0, contract),
+            )),
+            EventType::TD => Ok((
+                functions::pof_td_optns(event, &contract),
+                functions::stf_td_optns(event, &t0, contract),
+            )),
+            EventType::RR => Ok((
+                Self::pof_rr_capfl(event, &contract),
+                Self::stf_rr_capfl(event, &t0, contract),
+            )),
+            _ => Err("Event not applicable"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn progress_capfl_works() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 05, 00, 00, 00);
+            let underlying_id = H256::random();
+            let mut underlying = Terms::new(underlying_id);
+            underlying.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            underlying.contract_id = underlying_id;
+            underlying.contract_role = Some(ContractRole::RPA);
+            underlying.contract_type = Some(ContractType::PAM);
+            underlying.counterparty_id = Some(H256::random());
+            underlying.creator_id = Some(H256::random());
+            underlying.currency = Some(1);
+            underlying.day_count_convention = Some(DayCountConvention::_30E360);
+            underlying.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+            underlying.maturity_date = Time::from_values(2016, 01, 02, 00, 00, 00);
+            underlying.nominal_interest_rate = Real::from(5) / Real::from(100);
+            underlying.notional_principal = Real::from(1000);
+            underlying.cycle_of_rate_reset = Some(Cycle::Months(3, true));
+            underlying.market_object_code_rate_reset = Some(H256::random());
+            underlying.rate_spread = Real::from(0);
+            underlying.scaling_effect = None;
+            let contract = Contracts::deploy_pam(t0, underlying.clone()).unwrap();
+            <Contracts as Store>::Contracts::insert(underlying_id, contract);
+
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 05, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::CAPFL);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.life_cap = Real::from(5) / Real::from(100);
+            terms.contract_structure = vec![Some(ContractStructure {
+                _object: underlying_id,
+                _type: ContractReferenceType::Contract,
+                _role: ContractReferenceRole::Underlying,
+            })];
+            let moc = underlying.market_object_code_rate_reset.unwrap();
+
+            // The caplet pays the difference between the reset rate and the cap.
+            assert_ok!(<oracle::Module<Test>>::set(
+                moc,
+                Real::from(9) / Real::from(100)
+            ));
+            let contract = Contracts::deploy_capfl(t0, terms.clone()).unwrap();
+            let result = Contracts::progress_capfl(contract.schedule[0], contract).unwrap();
+            assert_eq!(result.0, Real::from(10));
+            assert_eq!(
+                result.1.states.nominal_interest_rate,
+                Real::from(9) / Real::from(100)
+            );
+
+            // Below the cap nothing is paid.
+            assert_ok!(<oracle::Module<Test>>::set(
+                moc,
+                Real::from(2) / Real::from(100)
+            ));
+            let contract = Contracts::deploy_capfl(t0, terms.clone()).unwrap();
+            let result = Contracts::progress_capfl(contract.schedule[0], contract).unwrap();
+            assert_eq!(result.0, Real::from(0));
+
+            // The floorlet pays the difference between the floor and the reset rate.
+            terms.life_cap = Real(None);
+            terms.life_floor = Real::from(4) / Real::from(100);
+            let contract = Contracts::deploy_capfl(t0, terms).unwrap();
+            let result = Contracts::progress_capfl(contract.schedule[0], contract).unwrap();
+            assert_eq!(result.0, Real::from(5));
+        });
+    }
+}
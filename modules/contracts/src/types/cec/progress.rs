@@ -0,0 +1,216 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    pub fn progress_cec(
+        event: ContractEvent,
+        contract: Contract,
+    ) -> ContractResult<(Real, Contract)> {
+        // Getting t0 from the status_date attribute since they are equal.
+        // (And status_date is not supposed to change)
+        let t0 = contract.terms.status_date;
+
+        // The exercise needs a covered credit event, and the settlement a previous exercise.
+        if event.event_type == EventType::XD {
+            if contract.states.exercise_date != Time(None) {
+                return Err("Credit enhancement has already been exercised");
+            }
+            if !Self::credit_event_occurred(&contract) {
+                return Err("No credit event of the covered contracts");
+            }
+        }
+        if event.event_type == EventType::STD && contract.states.exercise_date == Time(None) {
+            return Err("Credit enhancement hasn't been exercised");
+        }
+
+        match event.event_type {
+            EventType::PRD => Ok((
+                functions::pof_prd_optns(event, &contract),
+                functions::stf_prd_optns(event, &t0, contract),
+            )),
+            EventType::TD => Ok((
+                functions::pof_td_optns(event, &contract),
+                functions::stf_td_optns(event, &t0, contract),
+            )),
+            EventType::XD => Ok((
+                functions::pof_xd_ceg(event, &contract),
+                Self::stf_xd_cec(event, &t0, contract),
+            )),
+            EventType::STD => Ok((
+                functions::pof_std_ceg(event, &contract),
+                functions::stf_std_ceg(event, &t0, contract),
+            )),
+            EventType::MD => Ok((
+                functions::pof_md_ceg(event, &contract),
+                functions::stf_md_ceg(event, &t0, contract),
+            )),
+            _ => Err("Event not applicable"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn progress_cec_works() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 05, 00, 00, 00);
+            let covered_id = H256::random();
+            let mut covered = Terms::new(covered_id);
+            covered.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            covered.contract_id = covered_id;
+            covered.contract_role = Some(ContractRole::RPA);
+            covered.contract_type = Some(ContractType::PAM);
+            covered.counterparty_id = Some(H256::random());
+            covered.creator_id = Some(H256::random());
+            covered.currency = Some(1);
+            covered.day_count_convention = Some(DayCountConvention::_30E360);
+            covered.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+            covered.maturity_date = Time::from_values(2016, 01, 02, 00, 00, 00);
+            covered.nominal_interest_rate = Real::from(0);
+            covered.notional_principal = Real::from(1000);
+            covered.scaling_effect = None;
+            let contract = Contracts::deploy_pam(t0, covered.clone()).unwrap();
+            <Contracts as Store>::Contracts::insert(covered_id, contract);
+
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 05, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::CEC);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = covered.creator_id;
+            terms.currency = Some(1);
+            terms.settlement_currency = Some(1);
+            terms.guaranteed_exposure = Some(GuaranteedExposure::NO);
+            terms.coverage_of_credit_enhancement = Real::from(1) / Real::from(2);
+            terms.credit_event_type_covered = Some(CreditEventTypeCovered::DQ);
+            terms.contract_structure = vec![Some(ContractStructure {
+                _object: covered_id,
+                _type: ContractReferenceType::Contract,
+                _role: ContractReferenceRole::CoveredContract,
+            })];
+            let collateral_id = H256::random();
+            let mut collateral = Terms::new(collateral_id);
+            collateral.contract_id = collateral_id;
+            collateral.contract_role = Some(ContractRole::RPA);
+            collateral.contract_type = Some(ContractType::CSH);
+            collateral.creator_id = terms.counterparty_id;
+            collateral.currency = Some(1);
+            collateral.notional_principal = Real::from(300);
+            assert_ok!(<assets::Module<Test>>::mint(
+                terms.counterparty_id.unwrap(),
+                1,
+                Real::from(300)
+            ));
+            let contract = Contracts::deploy_csh(t0, collateral).unwrap();
+            <Contracts as Store>::Contracts::insert(collateral_id, contract);
+            terms.contract_structure.push(Some(ContractStructure {
+                _object: collateral_id,
+                _type: ContractReferenceType::Contract,
+                _role: ContractReferenceRole::CoveringContract,
+            }));
+            let contract = Contracts::deploy_cec(t0, terms).unwrap();
+            let mut covered = <Contracts as Store>::Contracts::get(covered_id);
+            covered.states.contract_performance = Some(ContractPerformance::DQ);
+            <Contracts as Store>::Contracts::insert(covered_id, covered);
+
+            // Half of the notional is covered, but the collateral is only worth 300.
+            let time = Time::from_values(2015, 02, 01, 00, 00, 00);
+            let result =
+                Contracts::progress_cec(ContractEvent::new(time, EventType::XD), contract).unwrap();
+            assert_eq!(result.1.states.exercise_amount, Real::from(300));
+            let result =
+                Contracts::progress_cec(ContractEvent::new(time, EventType::STD), result.1)
+                    .unwrap();
+            assert_eq!(result.0, Real::from(300));
+        });
+    }
+}
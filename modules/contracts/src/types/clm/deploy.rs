@@ -0,0 +1,372 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    pub fn deploy_clm(t0: Time, input: Terms) -> ContractResult<Contract> {
+        // The ContractID, necessary to create any contract.
+        let mut terms = Terms::new(input.contract_id);
+
+        // Setting the Status Date to t0, since we don't want terms to change.
+        terms.status_date = t0;
+
+        // Mandatory in all cases -> NN
+        if input.contract_type.is_none()
+            || input.currency.is_none()
+            || input.day_count_convention.is_none()
+            || input.initial_exchange_date.0.is_none()
+            || input.nominal_interest_rate.0.is_none()
+            || input.notional_principal.0.is_none()
+        {
+            return Err("Error while initializing terms. [0]");
+        } else {
+            terms.contract_type = input.contract_type;
+            terms.currency = input.currency;
+            terms.day_count_convention = input.day_count_convention;
+            terms.initial_exchange_date = input.initial_exchange_date;
+            terms.nominal_interest_rate = input.nominal_interest_rate;
+            terms.notional_principal = input.notional_principal;
+        }
+
+        // Mandatory on stand-alone and parent contracts only and
+        // not applicable on child contracts -> NN(_,_,1)
+        if input.contract_deal_date.0.is_none()
+            || input.contract_role.is_none()
+            || input.creator_id.is_none()
+        {
+            return Err("Error while initializing terms. [1]");
+        } else {
+            terms.contract_deal_date = input.contract_deal_date;
+            terms.contract_role = input.contract_role;
+            terms.creator_id = input.creator_id;
+        }
+
+        // Mandatory on stand-alone and parent contracts only and
+        // optional on child contracts -> NN(_,_,2)
+        if input.counterparty_id.is_none() {
+            return Err("Error while initializing terms. [2]");
+        } else {
+            terms.counterparty_id = input.counterparty_id;
+        }
+
+        // Optional in all cases -> x
+        terms.accrued_interest = input.accrued_interest;
+        terms.business_day_convention = input.business_day_convention;
+        terms.calendar = input.calendar;
+        terms.credit_line_amount = input.credit_line_amount;
+        terms.end_of_month_convention = input.end_of_month_convention;
+        terms.market_object_code = input.market_object_code;
+        terms.market_value_observed = input.market_value_observed;
+        terms.maturity_date = input.maturity_date;
+        terms.settlement_currency = input.settlement_currency;
+        terms.x_day_notice = input.x_day_notice;
+
+        // Optional on stand-alone and parent contracts only and
+        // not applicable on child contracts -> x(_,_,1)
+        terms.contract_performance = input.contract_performance;
+        terms.delinquency_period = input.delinquency_period;
+        terms.delinquency_rate = input.delinquency_rate;
+        terms.grace_period = input.grace_period;
+        terms.non_performing_date = input.non_performing_date;
+        terms.seniority = input.seniority;
+
+        // Group 1
+        // Business rule ‘a’ applies unconditionally
+        terms.fee_rate = input.fee_rate; // -> x(1,0,_)
+
+        // Business rule ‘a’ applies if at least one of the unconditional CAs of this group is defined
+        if input.fee_rate.0.is_some() {
+            if input.fee_basis.is_none() {
+                return Err("Error while initializing terms. [3]");
+            }
+            terms.fee_basis = input.fee_basis; // -> NN(1,1,_)
+            terms.fee_accrued = input.fee_accrued; // -> x(1,1,_)
+        }
+        // At least one of the CAs with c=2 has to be defined if at least one of the unconditional CAs
+        // of the group is defined
+        if input.fee_rate.0.is_some() {
+            if input.cycle_anchor_date_of_fee.0.is_none() && input.cycle_of_fee.is_none() {
+                return Err("Error while initializing terms. [4]");
+            }
+            terms.cycle_anchor_date_of_fee = input.cycle_anchor_date_of_fee; // -> x(1,2,_)
+            terms.cycle_of_fee = input.cycle_of_fee; // -> x(1,2,_)
+        }
+
+        // Group 2
+        // Business rule ‘a’ applies unconditionally
+        terms.cycle_anchor_date_of_interest_payment = input.cycle_anchor_date_of_interest_payment; // -> x(2,0,_)
+        terms.cycle_of_interest_payment = input.cycle_of_interest_payment; // -> x(2,0,_)
+
+        // Group 9
+        // Business rule ‘a’ applies unconditionally
+        terms.cycle_anchor_date_of_rate_reset = input.cycle_anchor_date_of_rate_reset; // -> x(9,0,_)
+        terms.cycle_of_rate_reset = input.cycle_of_rate_reset; // -> x(9,0,_)
+
+        // Business rule ‘a’ applies if at least one of the unconditional CAs of this group is defined
+        if input.cycle_anchor_date_of_rate_reset.0.is_some() || input.cycle_of_rate_reset.is_some()
+        {
+            if input.market_object_code_rate_reset.is_none() || input.rate_spread.0.is_none() {
+                return Err("Error while initializing terms. [5]");
+            } else {
+                terms.market_object_code_rate_reset = input.market_object_code_rate_reset; // -> NN(9,1,_)
+                terms.rate_spread = input.rate_spread; // -> NN(9,1,_)
+            }
+            terms.fixing_days = input.fixing_days; // -> x(9,1,_)
+            terms.life_cap = input.life_cap; // -> x(9,1,_)
+            terms.life_floor = input.life_floor; // -> x(9,1,_)
+            terms.period_cap = input.period_cap; // -> x(9,1,_)
+            terms.period_floor = input.period_floor; // -> x(9,1,_)
+            terms.rate_multiplier = input.rate_multiplier; // -> x(9,1,_)
+        }
+
+        // Contracts without a maturity date have their schedule created piece by piece, which
+        // requires every anchor date to come with a cycle.
+        if terms.maturity_date == Time(None)
+            && ((terms.cycle_anchor_date_of_fee != Time(None) && terms.cycle_of_fee.is_none())
+                || (terms.cycle_anchor_date_of_interest_payment != Time(None)
+                    && terms.cycle_of_interest_payment.is_none())
+                || (terms.cycle_anchor_date_of_rate_reset != Time(None)
+                    && terms.cycle_of_rate_reset.is_none()))
+        {
+            return Err("Error while initializing terms. [6]");
+        }
+
+        // Checking if the terms all have allowed values
+        if terms.is_valid() == false {
+            return Err("Error while initializing terms. [7]");
+        }
+
+        // Creating the schedule for all the events.
+        // Note: Principal redemptions, principal drawdowns and the termination of the contract are
+        // not scheduled, they are initiated by the users.
+        let mut schedule: Vec<ContractEvent> = Vec::new();
+
+        // Inital exchange date event
+        let event = ContractEvent::new(terms.initial_exchange_date, EventType::IED);
+        schedule.push(event);
+
+        // Maturity date and interest payment events
+        // Note: If the maturity date is defined, the whole schedule is created at once. Otherwise
+        // only its first piece is created and the Scheduler extends it when needed.
+        if terms.maturity_date == Time(None) {
+            let mut vec = open_ended_schedule(&terms, terms.initial_exchange_date)?;
+            schedule.append(&mut vec);
+        } else {
+            let event = ContractEvent::new(terms.maturity_date, EventType::IP);
+            schedule.push(event);
+            let event = ContractEvent::new(terms.maturity_date, EventType::MD);
+            schedule.push(event);
+            let mut vec =
+                open_ended_events(&terms, terms.initial_exchange_date, terms.maturity_date)?;
+            schedule.append(&mut vec);
+        }
+
+        // Ordering the schedule
+        schedule.sort_unstable();
+
+        // Initializing the contract states
+        let mut states = States::new();
+
+        // Time At Maturity Date variable
+        states.time_at_maturity_date = terms.maturity_date;
+
+        // Notional Principal variable
+        if terms.initial_exchange_date > t0 {
+            states.notional_principal = Real::from(0);
+        } else {
+            states.notional_principal =
+                utilities::contract_role_sign(terms.contract_role) * terms.notional_principal;
+        }
+
+        // Nominal Interest Rate variable
+        if terms.initial_exchange_date > t0 {
+            states.nominal_interest_rate = Real::from(0);
+        } else {
+            states.nominal_interest_rate = terms.nominal_interest_rate;
+        }
+
+        // Accrued Interest variable
+        if terms.accrued_interest != Real(None) {
+            states.accrued_interest = terms.accrued_interest;
+        } else {
+            states.accrued_interest = Real::from(0);
+        }
+
+        // Fee Accrued variable
+        if terms.fee_accrued != Real(None) {
+            states.fee_accrued = terms.fee_accrued;
+        } else {
+            states.fee_accrued = Real::from(0);
+        }
+
+        // Notional Scaling Multiplier variable
+        states.notional_scaling_multiplier = Real::from(1);
+
+        // Interest Scaling Multiplier variable
+        states.interest_scaling_multiplier = Real::from(1);
+
+        // Contract Performance variable
+        states.contract_performance = terms.contract_performance;
+
+        // Status Date variable
+        states.status_date = t0;
+
+        // Returning the initialized Contract State
+        Ok(Contract {
+            terms: terms,
+            states: states,
+            schedule: schedule,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn deploy_clm_works() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::CLM);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.day_count_convention = Some(DayCountConvention::_30E360);
+            terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+            terms.nominal_interest_rate = Real::from(12) / Real::from(100);
+            terms.notional_principal = Real::from(1000);
+            terms.cycle_anchor_date_of_interest_payment =
+                Time::from_values(2015, 02, 01, 00, 00, 00);
+
+            // An open-ended contract needs a cycle for every anchor date.
+            assert!(Contracts::deploy_clm(t0, terms.clone()).is_err());
+
+            // Without a maturity date only the first year of the schedule is created.
+            terms.cycle_of_interest_payment = Some(Cycle::Months(1, true));
+            let contract = Contracts::deploy_clm(t0, terms.clone()).unwrap();
+            assert_eq!(contract.schedule.len(), 13);
+            assert_eq!(
+                contract.schedule[1],
+                ContractEvent::new(Time::from_values(2015, 02, 01, 00, 00, 00), EventType::IPCI)
+            );
+            assert_eq!(
+                contract.schedule[12],
+                ContractEvent::new(Time::from_values(2016, 01, 01, 00, 00, 00), EventType::IPCI)
+            );
+            assert_eq!(contract.states.time_at_maturity_date, Time(None));
+
+            // With a maturity date the whole schedule is created.
+            terms.maturity_date = Time::from_values(2015, 04, 01, 00, 00, 00);
+            let contract = Contracts::deploy_clm(t0, terms).unwrap();
+            assert_eq!(
+                contract.schedule,
+                vec![
+                    ContractEvent::new(Time::from_values(2015, 01, 02, 00, 00, 00), EventType::IED),
+                    ContractEvent::new(
+                        Time::from_values(2015, 02, 01, 00, 00, 00),
+                        EventType::IPCI
+                    ),
+                    ContractEvent::new(
+                        Time::from_values(2015, 03, 01, 00, 00, 00),
+                        EventType::IPCI
+                    ),
+                    ContractEvent::new(Time::from_values(2015, 04, 01, 00, 00, 00), EventType::IP),
+                    ContractEvent::new(
+                        Time::from_values(2015, 04, 01, 00, 00, 00),
+                        EventType::IPCI
+                    ),
+                    ContractEvent::new(Time::from_values(2015, 04, 01, 00, 00, 00), EventType::MD),
+                ]
+            );
+        });
+    }
+}
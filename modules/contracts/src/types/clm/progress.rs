@@ -0,0 +1,252 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    pub fn progress_clm(
+        event: ContractEvent,
+        mut contract: Contract,
+    ) -> ContractResult<(Real, Contract)> {
+        // Getting t0 from the status_date attribute since they are equal.
+        // (And status_date is not supposed to change)
+        let t0 = contract.terms.status_date;
+
+        // Checking the events initiated by the users.
+        if event.event_type == EventType::PR
+            || event.event_type == EventType::PI
+            || event.event_type == EventType::MD
+        {
+            if event.time < contract.states.status_date {
+                return Err("Event is earlier than the last event of the contract");
+            }
+            if contract.states.time_at_maturity_date != Time(None)
+                && event.time > contract.states.time_at_maturity_date
+            {
+                return Err("Contract has already matured");
+            }
+        }
+        if event.event_type == EventType::PR || event.event_type == EventType::PI {
+            if event.amount == Real(None) || event.amount <= Real::from(0) {
+                return Err("Event amount must be positive");
+            }
+        }
+        if event.event_type == EventType::PI
+            && contract.terms.credit_line_amount != Real(None)
+            && contract.states.notional_principal.abs() + event.amount
+                > contract.terms.credit_line_amount
+        {
+            return Err("Drawdown exceeds the credit line amount");
+        }
+
+        // CLM has its own functions for the events initiated by the users and for the events that
+        // accrue fees, the remaining ones are shared with PAM.
+        match event.event_type {
+            EventType::IED => Ok((
+                functions::pof_ied_clm(event, &contract),
+                functions::stf_ied_pam(event, &t0, contract),
+            )),
+            EventType::PR => Ok((
+                functions::pof_pr_clm(event, &contract),
+                functions::stf_pr_clm(event, &t0, contract),
+            )),
+            EventType::PI => Ok((
+                functions::pof_pi_clm(event, &contract),
+                functions::stf_pi_clm(event, &t0, contract),
+            )),
+            EventType::FP => Ok((
+                functions::pof_fp_pam(event, &contract),
+                functions::stf_fp_pam(event, &t0, contract),
+            )),
+            EventType::IP => Ok((
+                functions::pof_ip_pam(event, &contract),
+                functions::stf_ip_pam(event, &t0, contract),
+            )),
+            EventType::IPCI => Ok((
+                functions::pof_ipci_pam(event, &contract),
+                functions::stf_ipci_clm(event, &t0, contract),
+            )),
+            EventType::RR => Ok((
+                functions::pof_rr_pam(event, &contract),
+                Self::stf_rr_clm(event, &t0, contract),
+            )),
+            EventType::CE => Ok((
+                functions::pof_ce_pam(event, &contract),
+                functions::stf_ce_pam(event, &t0, contract),
+            )),
+            EventType::MD => Ok((
+                functions::pof_md_clm(event, &contract),
+                functions::stf_md_clm(event, &t0, contract),
+            )),
+            _ => Err("Event not applicable"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn progress_clm_works() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::CLM);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.day_count_convention = Some(DayCountConvention::_30E360);
+            terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+            terms.nominal_interest_rate = Real::from(12) / Real::from(100);
+            terms.notional_principal = Real::from(1000);
+            terms.credit_line_amount = Real::from(1500);
+
+            let mut contract = Contracts::deploy_clm(t0, terms).unwrap();
+            assert_eq!(
+                contract.schedule,
+                vec![ContractEvent::new(
+                    Time::from_values(2015, 01, 02, 00, 00, 00),
+                    EventType::IED
+                )]
+            );
+            contract = Contracts::progress_clm(contract.schedule[0], contract)
+                .unwrap()
+                .1;
+            assert_eq!(contract.states.notional_principal, Real::from(1000));
+
+            // Drawdowns need a positive amount and are limited by the credit line.
+            let t = Time::from_values(2015, 02, 02, 00, 00, 00);
+            let event = ContractEvent::new(t, EventType::PI);
+            assert!(Contracts::progress_clm(event, contract.clone()).is_err());
+            let event = ContractEvent::with_amount(t, EventType::PI, Real::from(501));
+            assert!(Contracts::progress_clm(event, contract.clone()).is_err());
+
+            let event = ContractEvent::with_amount(t, EventType::PI, Real::from(500));
+            let result = Contracts::progress_clm(event, contract).unwrap();
+            assert_eq!(result.0, Real::from(-500));
+            contract = result.1;
+            assert_eq!(contract.states.notional_principal, Real::from(1500));
+            assert_eq!(contract.states.accrued_interest, Real::from(10));
+
+            // Principal redemptions can't be earlier than the last event.
+            let event = ContractEvent::with_amount(
+                Time::from_values(2015, 02, 01, 00, 00, 00),
+                EventType::PR,
+                Real::from(200),
+            );
+            assert!(Contracts::progress_clm(event, contract.clone()).is_err());
+
+            let t = Time::from_values(2015, 03, 02, 00, 00, 00);
+            let event = ContractEvent::with_amount(t, EventType::PR, Real::from(200));
+            let result = Contracts::progress_clm(event, contract).unwrap();
+            assert_eq!(result.0, Real::from(200));
+            contract = result.1;
+            assert_eq!(contract.states.notional_principal, Real::from(1300));
+            assert_eq!(contract.states.accrued_interest, Real::from(25));
+
+            // The termination repays the notional and the interest.
+            let t = Time::from_values(2015, 04, 02, 00, 00, 00);
+            let event = ContractEvent::new(t, EventType::MD);
+            let result = Contracts::progress_clm(event, contract).unwrap();
+            assert_eq!(result.0, Real::from(1338));
+            contract = result.1;
+            assert_eq!(contract.states.notional_principal, Real::from(0));
+            assert_eq!(contract.states.time_at_maturity_date, t);
+
+            // No events are possible after the termination.
+            let event = ContractEvent::with_amount(
+                Time::from_values(2015, 05, 02, 00, 00, 00),
+                EventType::PI,
+                Real::from(100),
+            );
+            assert!(Contracts::progress_clm(event, contract).is_err());
+        });
+    }
+}
@@ -0,0 +1,339 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+/// The maximum number of credit enhancements covering a contract.
+pub const MAX_CREDIT_ENHANCEMENTS: usize = 16;
+
+impl<T: Trait> Module<T> {
+    /// Returns the creditor of a contract, that is the party receiving its principal: the creator
+    /// of an asset (e.g. RPA) or the counterparty of a liability (e.g. RPL).
+    pub fn creditor(contract: &Contract) -> Option<H256> {
+        let sign = utilities::contract_role_sign(contract.terms.contract_role);
+        if sign == Real::from(1) {
+            contract.terms.creator_id
+        } else if sign == Real::from(-1) {
+            contract.terms.counterparty_id
+        } else {
+            None
+        }
+    }
+
+    /// Tells if a credit enhancement with the given terms can cover a contract. Only credit
+    /// enhancements that the creditor of the contract takes part in can cover it, and a contract
+    /// can only be covered by a limited number of them.
+    pub fn can_cover(covered_id: H256, covered: &Contract, terms: &Terms) -> bool {
+        let creditor = Self::creditor(covered);
+        creditor.is_some()
+            && (creditor == terms.creator_id || creditor == terms.counterparty_id)
+            && <Self as Store>::CreditEnhancements::get(covered_id).len() < MAX_CREDIT_ENHANCEMENTS
+    }
+
+    /// Returns the exposure of the contracts covered by a credit enhancement, according to its
+    /// guaranteed exposure (notional, notional plus interest or market value).
+    pub fn covered_exposure(contract: &Contract) -> Real {
+        let mut exposure = Real::from(0);
+        for structure in contract.terms.contract_structure.iter().flatten() {
+            if structure._role == ContractReferenceRole::CoveredContract {
+                let covered = <Self as Store>::Contracts::get(structure._object);
+                match contract.terms.guaranteed_exposure {
+                    Some(GuaranteedExposure::NO) => {
+                        exposure = exposure + covered.states.notional_principal.abs();
+                    }
+                    Some(GuaranteedExposure::NI) => {
+                        exposure = exposure + covered.states.notional_principal.abs();
+                        if covered.states.accrued_interest != Real(None) {
+                            exposure = exposure + covered.states.accrued_interest.abs();
+                        }
+                    }
+                    _ => {
+                        exposure = exposure + covered.terms.market_value_observed.abs();
+                    }
+                }
+            }
+        }
+        exposure
+    }
+
+    /// Returns the value at the given time of the positions covering a collateral.
+    pub fn covering_value(contract: &Contract, time: Time) -> Real {
+        let mut value = Real::from(0);
+        for structure in contract.terms.contract_structure.iter().flatten() {
+            if structure._role == ContractReferenceRole::CoveringContract {
+                let covering = <Self as Store>::Contracts::get(structure._object);
+                // Note: The covering contracts were checked to be positions at deployment.
+                value = value + Self::position_value(&covering, time).unwrap_or(Real::from(0));
+            }
+        }
+        value
+    }
+
+    /// Tells if a credit event covered by a credit enhancement happened to any of its covered
+    /// contracts.
+    pub fn credit_event_occurred(contract: &Contract) -> bool {
+        for structure in contract.terms.contract_structure.iter().flatten() {
+            if structure._role == ContractReferenceRole::CoveredContract {
+                let covered = <Self as Store>::Contracts::get(structure._object);
+                if utilities::credit_event_covered(
+                    covered.states.contract_performance,
+                    contract.terms.credit_event_type_covered,
+                ) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Notifies the credit enhancements covering a contract that its performance changed. The
+    /// credit enhancements for which the credit event is covered are exercised and settled by the
+    /// Scheduler, as events of their own, so that they don't affect the covered contract.
+    pub fn credit_event(contract_id: H256, time: Time) {
+        for id in <Self as Store>::CreditEnhancements::get(contract_id) {
+            let mut contract = <Self as Store>::Contracts::get(id);
+
+            // Credit enhancements that already matured, were exercised or are about to be are
+            // skipped.
+            if contract.states.exercise_date != Time(None)
+                || (contract.states.time_at_maturity_date != Time(None)
+                    && time > contract.states.time_at_maturity_date)
+                || contract
+                    .schedule
+                    .iter()
+                    .any(|event| event.event_type == EventType::XD)
+                || !Self::credit_event_occurred(&contract)
+            {
+                continue;
+            }
+
+            // Note: The settlement happens at the time of the exercise.
+            let index = contract
+                .schedule
+                .iter()
+                .position(|event| event.time > time)
+                .unwrap_or(contract.schedule.len());
+            contract
+                .schedule
+                .insert(index, ContractEvent::new(time, EventType::XD));
+            contract
+                .schedule
+                .insert(index + 1, ContractEvent::new(time, EventType::STD));
+            <Self as Store>::Contracts::insert(id, contract);
+
+            // The pending event of the credit enhancement is replaced by the exercise, unless it
+            // comes before it. A pending event at the index of the exercise now points to the
+            // exercise but with the time of the later event, so it is replaced too. Halted credit
+            // enhancements are exercised once they are resumed.
+            let pending_event = Self::pending_event(id);
+            let halted = <Self as Store>::SchedulerFailures::get(id).is_halted();
+            if !halted && pending_event.map_or(true, |event| event.index as usize >= index) {
+                Self::scheduler_push(ScheduledEvent {
+                    time: time,
+                    contract_id: id,
+                    index: index as u32,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn credit_event_works() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 05, 00, 00, 00);
+            let covered_id = H256::random();
+            let mut covered = Terms::new(covered_id);
+            covered.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            covered.contract_id = covered_id;
+            covered.contract_role = Some(ContractRole::RPA);
+            covered.contract_type = Some(ContractType::PAM);
+            covered.counterparty_id = Some(H256::random());
+            covered.creator_id = Some(H256::random());
+            covered.currency = Some(1);
+            covered.day_count_convention = Some(DayCountConvention::_30E360);
+            covered.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+            covered.maturity_date = Time::from_values(2016, 01, 02, 00, 00, 00);
+            covered.nominal_interest_rate = Real::from(0);
+            covered.notional_principal = Real::from(1000);
+            covered.scaling_effect = None;
+            let contract = Contracts::deploy_pam(t0, covered.clone()).unwrap();
+            <Contracts as Store>::Contracts::insert(covered_id, contract);
+
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 05, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::CEG);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = covered.creator_id;
+            terms.currency = Some(1);
+            terms.settlement_currency = Some(1);
+            terms.guaranteed_exposure = Some(GuaranteedExposure::NO);
+            terms.coverage_of_credit_enhancement = Real::from(1) / Real::from(2);
+            terms.credit_event_type_covered = Some(CreditEventTypeCovered::DQ);
+            terms.contract_structure = vec![Some(ContractStructure {
+                _object: covered_id,
+                _type: ContractReferenceType::Contract,
+                _role: ContractReferenceRole::CoveredContract,
+            })];
+            let contract = Contracts::deploy_ceg(t0, terms.clone()).unwrap();
+            <Contracts as Store>::Contracts::insert(id, contract);
+            <Contracts as Store>::CreditEnhancements::insert(covered_id, vec![id]);
+            assert_ok!(<assets::Module<Test>>::mint(
+                terms.counterparty_id.unwrap(),
+                1,
+                Real::from(1000)
+            ));
+            <timestamp::Module<Test>>::set_timestamp(1_420_416_000);
+            Contracts::scheduler_push(ScheduledEvent {
+                time: Time::from_values(2016, 01, 02, 00, 00, 00),
+                contract_id: id,
+                index: 0,
+            });
+
+            // A delay isn't covered.
+            let time = Time::from_values(2015, 02, 01, 00, 00, 00);
+            let mut contract = <Contracts as Store>::Contracts::get(covered_id);
+            contract.states.contract_performance = Some(ContractPerformance::DL);
+            <Contracts as Store>::Contracts::insert(covered_id, contract.clone());
+            Contracts::credit_event(covered_id, time);
+            assert_eq!(<Contracts as Store>::Contracts::get(id).schedule.len(), 1);
+
+            // A delinquency is, so the guarantee is exercised and settled by the Scheduler, before
+            // its maturity. The exercise is inserted at the index of the pending maturity, whose
+            // scheduled event is replaced by the exercise.
+            assert_eq!(Contracts::pending_event(id).unwrap().index, 0);
+            contract.states.contract_performance = Some(ContractPerformance::DQ);
+            <Contracts as Store>::Contracts::insert(covered_id, contract);
+            Contracts::credit_event(covered_id, time);
+            assert_eq!(
+                <Contracts as Store>::Contracts::get(id).schedule,
+                vec![
+                    ContractEvent::new(time, EventType::XD),
+                    ContractEvent::new(time, EventType::STD),
+                    ContractEvent::new(Time::from_values(2016, 01, 02, 00, 00, 00), EventType::MD),
+                ]
+            );
+            assert_eq!(
+                Contracts::pending_event(id),
+                Some(ScheduledEvent {
+                    time: time,
+                    contract_id: id,
+                    index: 0,
+                })
+            );
+            assert_eq!(
+                <assets::Module<Test>>::balances((1, terms.creator_id.unwrap())),
+                Real::from(0)
+            );
+
+            // Half of the notional is paid by the guarantor.
+            <timestamp::Module<Test>>::set_timestamp(1_422_748_800);
+            assert_ok!(Contracts::scheduler_run(time));
+            let contract = <Contracts as Store>::Contracts::get(id);
+            assert_eq!(contract.states.exercise_date, time);
+            assert_eq!(contract.states.time_at_maturity_date, time);
+            assert_eq!(
+                <assets::Module<Test>>::balances((1, terms.creator_id.unwrap())),
+                Real::from(500)
+            );
+
+            // It is only exercised once.
+            Contracts::credit_event(covered_id, time);
+            assert_ok!(Contracts::scheduler_run(time));
+            assert_eq!(<Contracts as Store>::Contracts::get(id).schedule.len(), 3);
+            assert_eq!(
+                <assets::Module<Test>>::balances((1, terms.creator_id.unwrap())),
+                Real::from(500)
+            );
+        });
+    }
+}
@@ -0,0 +1,195 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    pub fn deploy_csh(t0: Time, input: Terms) -> ContractResult<Contract> {
+        // The ContractID, necessary to create any contract.
+        let mut terms = Terms::new(input.contract_id);
+
+        // Setting the Status Date to t0, since we don't want terms to change.
+        terms.status_date = t0;
+
+        // Mandatory in all cases -> NN
+        if input.contract_role.is_none()
+            || input.contract_type.is_none()
+            || input.currency.is_none()
+            || input.notional_principal.0.is_none()
+        {
+            return Err("Error while initializing terms. [0]");
+        } else {
+            terms.contract_role = input.contract_role;
+            terms.contract_type = input.contract_type;
+            terms.currency = input.currency;
+            terms.notional_principal = input.notional_principal;
+        }
+
+        // Mandatory on stand-alone and parent contracts only and
+        // not applicable on child contracts -> NN(_,_,1)
+        if input.creator_id.is_none() {
+            return Err("Error while initializing terms. [1]");
+        } else {
+            terms.creator_id = input.creator_id;
+        }
+
+        // Optional in all cases -> x
+        terms.settlement_currency = input.settlement_currency;
+
+        // Checking if the terms all have allowed values
+        if terms.is_valid() == false {
+            return Err("Error while initializing terms. [2]");
+        }
+
+        // A cash position wraps a balance of the assets module, so it has to be covered by the
+        // balance of the creator.
+        if <assets::Module<T>>::balances((terms.currency.unwrap(), terms.creator_id.unwrap()))
+            < terms.notional_principal
+        {
+            return Err("Error while initializing terms. [3]");
+        }
+
+        // Creating the schedule for all the events.
+        // Note: A cash position has no events.
+        let schedule: Vec<ContractEvent> = Vec::new();
+
+        // Initializing the contract states
+        let mut states = States::new();
+
+        // Notional Principal variable
+        states.notional_principal =
+            utilities::contract_role_sign(terms.contract_role) * terms.notional_principal;
+
+        // Contract Performance variable
+        states.contract_performance = terms.contract_performance;
+
+        // Status Date variable
+        states.status_date = t0;
+
+        // Returning the initialized Contract State
+        Ok(Contract {
+            terms: terms,
+            states: states,
+            schedule: schedule,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn deploy_csh_works() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::CSH);
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.notional_principal = Real::from(1000);
+
+            // The position has to be covered by the balance of the creator.
+            assert_ok!(<assets::Module<Test>>::mint(
+                terms.creator_id.unwrap(),
+                1,
+                Real::from(500)
+            ));
+            assert!(Contracts::deploy_csh(t0, terms.clone()).is_err());
+            assert_ok!(<assets::Module<Test>>::mint(
+                terms.creator_id.unwrap(),
+                1,
+                Real::from(500)
+            ));
+
+            let contract = Contracts::deploy_csh(t0, terms).unwrap();
+            assert!(contract.schedule.is_empty());
+            assert_eq!(contract.states.notional_principal, Real::from(1000));
+        });
+    }
+}
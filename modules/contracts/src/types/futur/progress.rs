@@ -0,0 +1,222 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    pub fn progress_futur(
+        event: ContractEvent,
+        contract: Contract,
+    ) -> ContractResult<(Real, Contract)> {
+        // Getting t0 from the status_date attribute since they are equal.
+        // (And status_date is not supposed to change)
+        let t0 = contract.terms.status_date;
+
+        // FUTUR has its own functions for the margining and the exercise, the remaining ones are
+        // shared with OPTNS.
+        match event.event_type {
+            EventType::PRD => Ok((
+                functions::pof_prd_optns(event, &contract),
+                functions::stf_prd_optns(event, &t0, contract),
+            )),
+            EventType::TD => Ok((
+                functions::pof_td_optns(event, &contract),
+                functions::stf_td_optns(event, &t0, contract),
+            )),
+            EventType::MR => Ok((
+                Self::pof_mr_futur(event, &contract),
+                Self::stf_mr_futur(event, &t0, contract),
+            )),
+            EventType::XD => Ok((
+                functions::pof_xd_optns(event, &contract),
+                Self::stf_xd_futur(event, &t0, contract),
+            )),
+            EventType::STD => Ok((
+                functions::pof_std_optns(event, &contract),
+                functions::stf_std_optns(event, &t0, contract),
+            )),
+            _ => Err("Event not applicable"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn progress_futur_works() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.settlement_currency = Some(1);
+            terms.market_object_code = Some(H256::random());
+            terms.quantity = Real::from(10);
+            terms.contract_type = Some(ContractType::FUTUR);
+            terms.maturity_date = Time::from_values(2015, 01, 05, 00, 00, 00);
+            terms.futures_price = Real::from(100);
+            terms.cycle_of_margining = Some(Cycle::Days(1, true));
+            terms.contract_structure = vec![Some(ContractStructure {
+                _object: terms.market_object_code.unwrap(),
+                _type: ContractReferenceType::MarketObjectIdentifier,
+                _role: ContractReferenceRole::Underlying,
+            })];
+            let mut contract = Contracts::deploy_futur(t0, terms.clone()).unwrap();
+
+            // The variation margin follows the price of the underlying.
+            assert_ok!(<oracle::Module<Test>>::set(
+                terms.market_object_code.unwrap(),
+                Real::from(105)
+            ));
+            let result = Contracts::progress_futur(contract.schedule[0], contract).unwrap();
+            assert_eq!(result.0, Real::from(50));
+            contract = result.1;
+            assert_ok!(<oracle::Module<Test>>::set(
+                terms.market_object_code.unwrap(),
+                Real::from(102)
+            ));
+            let result = Contracts::progress_futur(contract.schedule[1], contract).unwrap();
+            assert_eq!(result.0, Real::from(-30));
+            contract = result.1;
+            let result = Contracts::progress_futur(contract.schedule[2], contract).unwrap();
+            assert_eq!(result.0, Real::from(0));
+            contract = result.1;
+
+            // The remaining difference is settled at maturity.
+            assert_ok!(<oracle::Module<Test>>::set(
+                terms.market_object_code.unwrap(),
+                Real::from(110)
+            ));
+            contract = Contracts::progress_futur(contract.schedule[3], contract)
+                .unwrap()
+                .1;
+            assert_eq!(contract.states.exercise_amount, Real::from(80));
+            let result = Contracts::progress_futur(contract.schedule[4], contract).unwrap();
+            assert_eq!(result.0, Real::from(80));
+        });
+    }
+
+    #[test]
+    fn progress_payoff_fails_without_oracle_value() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.settlement_currency = Some(1);
+            terms.market_object_code = Some(H256::random());
+            terms.quantity = Real::from(10);
+            terms.contract_type = Some(ContractType::FUTUR);
+            terms.maturity_date = Time::from_values(2015, 01, 05, 00, 00, 00);
+            terms.futures_price = Real::from(100);
+            terms.cycle_of_margining = Some(Cycle::Days(1, true));
+            terms.contract_structure = vec![Some(ContractStructure {
+                _object: terms.market_object_code.unwrap(),
+                _type: ContractReferenceType::MarketObjectIdentifier,
+                _role: ContractReferenceRole::Underlying,
+            })];
+            let contract = Contracts::deploy_futur(t0, terms).unwrap();
+
+            // Neither the margining nor the exercise can be calculated without the price of the
+            // underlying.
+            assert_eq!(contract.schedule[0].event_type, EventType::MR);
+            assert!(Contracts::progress_payoff(contract.schedule[0], contract.clone()).is_err());
+            assert_eq!(contract.schedule[3].event_type, EventType::XD);
+            assert!(Contracts::progress_payoff(contract.schedule[3], contract).is_err());
+        });
+    }
+}
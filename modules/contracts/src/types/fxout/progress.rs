@@ -0,0 +1,274 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    /// Progresses an FX outright. It returns the payoffs in the first and in the second currency
+    /// separately, since they are settled in different assets.
+    pub fn progress_fxout(
+        event: ContractEvent,
+        mut contract: Contract,
+    ) -> ContractResult<(Real, Real, Contract)> {
+        // Getting t0 from the status_date attribute since they are equal.
+        // (And status_date is not supposed to change)
+        let t0 = contract.terms.status_date;
+
+        // The cash settlement reads the exchange rate at the time of the event.
+        Self::check_oracle_values(event, &contract)?;
+
+        match event.event_type {
+            EventType::PRD => Ok((
+                functions::pof_prd_fxout(event, &contract),
+                Real::from(0),
+                functions::stf_prd_fxout(event, &t0, contract),
+            )),
+            EventType::TD => Ok((
+                functions::pof_td_fxout(event, &contract),
+                Real::from(0),
+                functions::stf_td_fxout(event, &t0, contract),
+            )),
+            EventType::MD => Ok((
+                functions::pof_md1_fxout(event, &contract),
+                functions::pof_md2_fxout(event, &contract),
+                functions::stf_md_fxout(event, &t0, contract),
+            )),
+            EventType::STD => Ok((
+                Self::pof_std_fxout(event, &contract),
+                Real::from(0),
+                functions::stf_std_fxout(event, &t0, contract),
+            )),
+            _ => Err("Event not applicable"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn progress_fxout_works() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::FXOUT);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.currency_2 = Some(2);
+            terms.maturity_date = Time::from_values(2015, 04, 01, 00, 00, 00);
+            terms.notional_principal = Real::from(1000);
+            terms.notional_principal_2 = Real::from(900);
+            terms.market_object_code = Some(H256::random());
+            terms.settlement_days = Some(Period::Days(2));
+            assert_ok!(<oracle::Module<Test>>::set(
+                terms.market_object_code.unwrap(),
+                Real::from(12) / Real::from(10)
+            ));
+
+            // Delivery.
+            terms.delivery_settlement = Some(DeliverySettlement::D);
+            let contract = Contracts::deploy_fxout(t0, terms.clone()).unwrap();
+            let result = Contracts::progress_fxout(contract.schedule[0], contract).unwrap();
+            assert_eq!(result.0, Real::from(1000));
+            assert_eq!(result.1, Real::from(-900));
+            assert_eq!(result.2.states.notional_principal, Real::from(0));
+
+            // Cash settlement, the second notional is worth 1080 in the first currency.
+            terms.delivery_settlement = Some(DeliverySettlement::S);
+            let contract = Contracts::deploy_fxout(t0, terms.clone()).unwrap();
+            let result = Contracts::progress_fxout(contract.schedule[0], contract).unwrap();
+            assert_eq!(result.0, Real::from(-80));
+            assert_eq!(result.1, Real::from(0));
+        });
+    }
+
+    #[test]
+    fn progress_settles_fxout() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::FXOUT);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.currency_2 = Some(2);
+            terms.maturity_date = Time::from_values(2015, 04, 01, 00, 00, 00);
+            terms.notional_principal = Real::from(1000);
+            terms.notional_principal_2 = Real::from(900);
+            terms.market_object_code = Some(H256::random());
+            terms.settlement_days = Some(Period::Days(2));
+            terms.delivery_settlement = Some(DeliverySettlement::D);
+            let creator_id = terms.creator_id.unwrap();
+            let counterparty_id = terms.counterparty_id.unwrap();
+            assert_ok!(<assets::Module<Test>>::mint(creator_id, 2, Real::from(900)));
+            assert_ok!(<assets::Module<Test>>::mint(
+                counterparty_id,
+                1,
+                Real::from(1000)
+            ));
+            let contract = Contracts::deploy_fxout(t0, terms.clone()).unwrap();
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+
+            // Each notional is transferred in its own currency.
+            assert_ok!(Contracts::progress(contract.schedule[0], id));
+            assert_eq!(
+                <assets::Module<Test>>::balances((1, creator_id)),
+                Real::from(1000)
+            );
+            assert_eq!(
+                <assets::Module<Test>>::balances((2, counterparty_id)),
+                Real::from(900)
+            );
+
+            // Neither notional is transferred if one of them can't be.
+            let id = H256::random();
+            terms.contract_id = id;
+            assert_ok!(<assets::Module<Test>>::mint(
+                counterparty_id,
+                1,
+                Real::from(1000)
+            ));
+            let contract = Contracts::deploy_fxout(t0, terms).unwrap();
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+            assert_ok!(Contracts::progress(contract.schedule[0], id));
+            assert_eq!(
+                <assets::Module<Test>>::balances((1, counterparty_id)),
+                Real::from(1000)
+            );
+            assert_eq!(
+                <Contracts as Store>::Contracts::get(id)
+                    .states
+                    .contract_performance,
+                Some(ContractPerformance::DL)
+            );
+        });
+    }
+
+    #[test]
+    fn progress_fails_without_oracle_value() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_performance = Some(ContractPerformance::PF);
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::FXOUT);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.currency_2 = Some(2);
+            terms.maturity_date = Time::from_values(2015, 04, 01, 00, 00, 00);
+            terms.notional_principal = Real::from(1000);
+            terms.notional_principal_2 = Real::from(900);
+            terms.market_object_code = Some(H256::random());
+            terms.settlement_days = Some(Period::Days(2));
+            terms.delivery_settlement = Some(DeliverySettlement::S);
+            let contract = Contracts::deploy_fxout(t0, terms).unwrap();
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+
+            // The payoff of the settlement can't be calculated, that isn't a missed payment.
+            assert!(Contracts::progress(contract.schedule[0], id).is_err());
+            let contract = <Contracts as Store>::Contracts::get(id);
+            assert_eq!(
+                contract.states.contract_performance,
+                Some(ContractPerformance::PF)
+            );
+            assert_eq!(contract.states.non_performing_date, Time(None));
+            assert!(!<Contracts as Store>::Arrears::exists(id));
+        });
+    }
+}
@@ -14,7 +14,43 @@
 use super::*;
 
 mod ann;
+mod capfl;
+mod cec;
+mod ceg;
+mod clm;
+mod com;
+mod credit_enhancement;
+mod csh;
+mod futur;
+mod fxout;
+mod lam;
+mod lax;
+mod nam;
+mod optns;
 mod pam;
+mod stk;
+mod swaps;
+mod swppv;
+mod ump;
+mod underlying;
 
 pub use ann::*;
+pub use capfl::*;
+pub use cec::*;
+pub use ceg::*;
+pub use clm::*;
+pub use com::*;
+pub use credit_enhancement::*;
+pub use csh::*;
+pub use futur::*;
+pub use fxout::*;
+pub use lam::*;
+pub use lax::*;
+pub use nam::*;
+pub use optns::*;
 pub use pam::*;
+pub use stk::*;
+pub use swaps::*;
+pub use swppv::*;
+pub use ump::*;
+pub use underlying::*;
@@ -0,0 +1,352 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    pub fn deploy_optns(t0: Time, input: Terms) -> ContractResult<Contract> {
+        // The ContractID, necessary to create any contract.
+        let mut terms = Terms::new(input.contract_id);
+
+        // Setting the Status Date to t0, since we don't want terms to change.
+        terms.status_date = t0;
+
+        // Mandatory in all cases -> NN
+        if input.contract_type.is_none()
+            || input.currency.is_none()
+            || input.market_object_code.is_none()
+            || input.maturity_date.0.is_none()
+            || input.option_execution_type.is_none()
+            || input.option_strike_1.0.is_none()
+            || input.option_type.is_none()
+        {
+            return Err("Error while initializing terms. [0]");
+        } else {
+            terms.contract_type = input.contract_type;
+            terms.currency = input.currency;
+            terms.market_object_code = input.market_object_code;
+            terms.maturity_date = input.maturity_date;
+            terms.option_execution_type = input.option_execution_type;
+            terms.option_strike_1 = input.option_strike_1;
+            terms.option_type = input.option_type;
+        }
+
+        // Mandatory on stand-alone and parent contracts only and
+        // not applicable on child contracts -> NN(_,_,1)
+        if input.contract_deal_date.0.is_none()
+            || input.contract_role.is_none()
+            || input.creator_id.is_none()
+        {
+            return Err("Error while initializing terms. [1]");
+        } else {
+            terms.contract_deal_date = input.contract_deal_date;
+            terms.contract_role = input.contract_role;
+            terms.creator_id = input.creator_id;
+        }
+
+        // Mandatory on stand-alone and parent contracts only and
+        // optional on child contracts -> NN(_,_,2)
+        if input.counterparty_id.is_none() {
+            return Err("Error while initializing terms. [2]");
+        } else {
+            terms.counterparty_id = input.counterparty_id;
+        }
+
+        // Optional in all cases -> x
+        terms.business_day_convention = input.business_day_convention;
+        terms.calendar = input.calendar;
+        terms.end_of_month_convention = input.end_of_month_convention;
+        terms.market_value_observed = input.market_value_observed;
+        terms.quantity = input.quantity;
+        terms.settlement_currency = input.settlement_currency;
+        terms.settlement_days = input.settlement_days;
+
+        // Optional on stand-alone and parent contracts only and
+        // not applicable on child contracts -> x(_,_,1)
+        terms.contract_performance = input.contract_performance;
+        terms.non_performing_date = input.non_performing_date;
+        terms.seniority = input.seniority;
+
+        // Contract structure
+        // The underlying must be referenced exactly once, and if it is a contract it must exist.
+        let mut underlyings = 0;
+        for structure in input.contract_structure.iter().flatten() {
+            if structure._role == ContractReferenceRole::Underlying {
+                if (structure._type == ContractReferenceType::Contract
+                    || structure._type == ContractReferenceType::ContractIdentifier)
+                    && !<Self as Store>::Contracts::exists(structure._object)
+                {
+                    return Err("Error while initializing terms. [3]");
+                }
+                underlyings += 1;
+            }
+        }
+        if underlyings != 1 {
+            return Err("Error while initializing terms. [3]");
+        } else {
+            terms.contract_structure = input.contract_structure;
+        }
+
+        // Collars need the second strike.
+        if terms.option_type == Some(OptionType::CP) {
+            if input.option_strike_2.0.is_none() {
+                return Err("Error while initializing terms. [4]");
+            } else {
+                terms.option_strike_2 = input.option_strike_2;
+            }
+        }
+
+        // American and bermudan options can be exercised before maturity, bermudan options only
+        // at the dates given by the optionality cycle.
+        terms.option_exercise_end_date = input.option_exercise_end_date;
+        if terms.option_execution_type == Some(OptionExecutionType::B) {
+            if input.cycle_of_optionality.is_none() {
+                return Err("Error while initializing terms. [5]");
+            } else {
+                terms.cycle_anchor_date_of_optionality = input.cycle_anchor_date_of_optionality;
+                terms.cycle_of_optionality = input.cycle_of_optionality;
+            }
+        }
+
+        // Group 5
+        // Business rule ‘a’ applies unconditionally
+        terms.purchase_date = input.purchase_date; // -> x(5,0,1)
+
+        // Business rule ‘a’ applies if at least one of the unconditional CAs of this group is defined
+        if input.purchase_date.0.is_some() {
+            if input.price_at_purchase_date.0.is_none() {
+                return Err("Error while initializing terms. [6]");
+            } else {
+                terms.price_at_purchase_date = input.price_at_purchase_date;
+                // -> NN(5,1,1)
+            }
+        }
+
+        // Group 6
+        // Business rule ‘a’ applies unconditionally
+        terms.termination_date = input.termination_date; // -> x(6,0,1)
+
+        // Business rule ‘a’ applies if at least one of the unconditional CAs of this group is defined
+        if input.termination_date.0.is_some() {
+            if input.price_at_termination_date.0.is_none() {
+                return Err("Error while initializing terms. [7]");
+            } else {
+                terms.price_at_termination_date = input.price_at_termination_date;
+                // -> NN(6,1,1)
+            }
+        }
+
+        // Checking if the terms all have allowed values
+        if terms.is_valid() == false {
+            return Err("Error while initializing terms. [8]");
+        }
+
+        // Creating the schedule for all the events.
+        let mut schedule: Vec<ContractEvent> = Vec::new();
+
+        // Purchase date event
+        let event = ContractEvent::new(terms.purchase_date, EventType::PRD);
+        schedule.push(event);
+
+        // Termination date event
+        let event = ContractEvent::new(terms.termination_date, EventType::TD);
+        schedule.push(event);
+
+        // Exercise date event
+        // Note: The contract is exercised automatically at maturity, unless it was exercised before.
+        let event = ContractEvent::new(terms.maturity_date, EventType::XD);
+        schedule.push(event);
+
+        // Settlement date event
+        let t = utilities::sum_period(
+            terms.maturity_date,
+            terms.settlement_days,
+            terms.end_of_month_convention,
+        );
+        let event = ContractEvent::new(t, EventType::STD);
+        schedule.push(event);
+
+        // Remove any events with Time == None
+        // Note: The unusual control flow is because we want to use the swap_remove method,
+        // which has O(1) complexity but requires a more complex solution to work.
+        let mut i = 0;
+        while i < schedule.len() {
+            if schedule[i].time == Time(None) {
+                schedule.swap_remove(i);
+            } else {
+                i += 1;
+            }
+        }
+
+        // Ordering the schedule
+        schedule.sort_unstable();
+
+        // Initializing the contract states
+        let mut states = States::new();
+
+        // Time At Maturity Date variable
+        // Note: The settlement event is the last event of the contract.
+        states.time_at_maturity_date = t;
+
+        // Exercise variables
+        states.exercise_amount = Real::from(0);
+        states.exercise_date = Time(None);
+
+        // Contract Performance variable
+        states.contract_performance = terms.contract_performance;
+
+        // Status Date variable
+        states.status_date = t0;
+
+        // Returning the initialized Contract State
+        Ok(Contract {
+            terms: terms,
+            states: states,
+            schedule: schedule,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn deploy_optns_works() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.settlement_currency = Some(1);
+            terms.market_object_code = Some(H256::random());
+            terms.quantity = Real::from(10);
+            terms.contract_type = Some(ContractType::OPTNS);
+            terms.maturity_date = Time::from_values(2015, 04, 01, 00, 00, 00);
+            terms.option_execution_type = Some(OptionExecutionType::E);
+            terms.option_type = Some(OptionType::C);
+            terms.option_strike_1 = Real::from(100);
+            terms.settlement_days = Some(Period::Days(2));
+            terms.contract_structure = vec![Some(ContractStructure {
+                _object: terms.market_object_code.unwrap(),
+                _type: ContractReferenceType::MarketObjectIdentifier,
+                _role: ContractReferenceRole::Underlying,
+            })];
+
+            // The underlying is mandatory.
+            let mut no_underlying = terms.clone();
+            no_underlying.contract_structure = Vec::new();
+            assert!(Contracts::deploy_optns(t0, no_underlying).is_err());
+
+            // Collars need the second strike.
+            let mut collar = terms.clone();
+            collar.option_type = Some(OptionType::CP);
+            assert!(Contracts::deploy_optns(t0, collar).is_err());
+
+            // Bermudan options need the optionality cycle.
+            let mut bermudan = terms.clone();
+            bermudan.option_execution_type = Some(OptionExecutionType::B);
+            assert!(Contracts::deploy_optns(t0, bermudan).is_err());
+
+            let contract = Contracts::deploy_optns(t0, terms).unwrap();
+            assert_eq!(
+                contract.schedule,
+                vec![
+                    ContractEvent::new(Time::from_values(2015, 04, 01, 00, 00, 00), EventType::XD),
+                    ContractEvent::new(Time::from_values(2015, 04, 03, 00, 00, 00), EventType::STD),
+                ]
+            );
+            assert_eq!(
+                contract.states.time_at_maturity_date,
+                Time::from_values(2015, 04, 03, 00, 00, 00)
+            );
+        });
+    }
+}
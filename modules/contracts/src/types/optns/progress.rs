@@ -0,0 +1,359 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    pub fn progress_optns(
+        event: ContractEvent,
+        mut contract: Contract,
+    ) -> ContractResult<(Real, Contract)> {
+        // Getting t0 from the status_date attribute since they are equal.
+        // (And status_date is not supposed to change)
+        let t0 = contract.terms.status_date;
+
+        // Checking the exercises initiated by the users, which are the ones not in the schedule.
+        let exercised_by_holder =
+            event.event_type == EventType::XD && !contract.schedule.contains(&event);
+        if exercised_by_holder {
+            Self::optns_exercisable(event.time, &contract)?;
+        }
+
+        // An option that was exercised before maturity isn't exercised again.
+        if event.event_type == EventType::XD && contract.states.exercise_date != Time(None) {
+            contract.states.status_date = event.time;
+            return Ok((Real::from(0), contract));
+        }
+
+        match event.event_type {
+            EventType::PRD => Ok((
+                functions::pof_prd_optns(event, &contract),
+                functions::stf_prd_optns(event, &t0, contract),
+            )),
+            EventType::TD => Ok((
+                functions::pof_td_optns(event, &contract),
+                functions::stf_td_optns(event, &t0, contract),
+            )),
+            EventType::XD => {
+                let payoff = functions::pof_xd_optns(event, &contract);
+                let mut contract = Self::stf_xd_optns(event, &t0, contract);
+                if exercised_by_holder {
+                    contract = optns_settlement(contract);
+                }
+                Ok((payoff, contract))
+            }
+            EventType::STD => Ok((
+                functions::pof_std_optns(event, &contract),
+                functions::stf_std_optns(event, &t0, contract),
+            )),
+            _ => Err("Event not applicable"),
+        }
+    }
+
+    /// Checks if an option can be exercised by its holder at the given time, before maturity.
+    /// American options can be exercised until the end of the exercise period, bermudan options
+    /// only at the dates given by the optionality cycle and european options only at maturity.
+    pub fn optns_exercisable(time: Time, contract: &Contract) -> Result {
+        if contract.states.exercise_date != Time(None) {
+            return Err("Option has already been exercised");
+        }
+        if time < contract.states.status_date {
+            return Err("Event is earlier than the last event of the contract");
+        }
+        let end = if contract.terms.option_exercise_end_date == Time(None) {
+            contract.terms.maturity_date
+        } else {
+            contract.terms.option_exercise_end_date
+        };
+        if time > end {
+            return Err("Exercise period has ended");
+        }
+        match contract.terms.option_execution_type {
+            Some(OptionExecutionType::A) => Ok(()),
+            Some(OptionExecutionType::B) => {
+                let mut s: Time = Time(None);
+                if contract.terms.cycle_anchor_date_of_optionality == Time(None) {
+                    s = utilities::sum_cycle(
+                        contract.terms.contract_deal_date,
+                        contract.terms.cycle_of_optionality,
+                        contract.terms.end_of_month_convention,
+                    );
+                } else {
+                    s = contract.terms.cycle_anchor_date_of_optionality;
+                }
+                let vec = utilities::schedule(
+                    s,
+                    end,
+                    contract.terms.cycle_of_optionality,
+                    contract.terms.end_of_month_convention,
+                )?;
+                if !vec.contains(&time) {
+                    return Err("Option can't be exercised at this time");
+                }
+                Ok(())
+            }
+            _ => Err("Option can only be exercised at maturity"),
+        }
+    }
+}
+
+/// Moves the settlement of an option exercised by its holder before maturity to the end of the
+/// settlement period after the exercise, which becomes the maturity of the contract. The exercise
+/// at maturity is removed from the schedule.
+fn optns_settlement(mut contract: Contract) -> Contract {
+    let t = utilities::sum_period(
+        contract.states.exercise_date,
+        contract.terms.settlement_days,
+        contract.terms.end_of_month_convention,
+    );
+    contract
+        .schedule
+        .retain(|e| e.event_type != EventType::XD && e.event_type != EventType::STD);
+    let event = ContractEvent::new(t, EventType::STD);
+    contract.schedule.push(event);
+    contract.schedule.sort_unstable();
+    contract.states.time_at_maturity_date = t;
+    contract
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn progress_optns_works() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.settlement_currency = Some(1);
+            terms.market_object_code = Some(H256::random());
+            terms.quantity = Real::from(10);
+            terms.contract_type = Some(ContractType::OPTNS);
+            terms.maturity_date = Time::from_values(2015, 04, 01, 00, 00, 00);
+            terms.option_execution_type = Some(OptionExecutionType::E);
+            terms.option_type = Some(OptionType::C);
+            terms.option_strike_1 = Real::from(100);
+            terms.settlement_days = Some(Period::Days(2));
+            terms.contract_structure = vec![Some(ContractStructure {
+                _object: terms.market_object_code.unwrap(),
+                _type: ContractReferenceType::MarketObjectIdentifier,
+                _role: ContractReferenceRole::Underlying,
+            })];
+            assert_ok!(<oracle::Module<Test>>::set(
+                terms.market_object_code.unwrap(),
+                Real::from(120)
+            ));
+
+            // A call is exercised at maturity and settled after the settlement days.
+            let mut contract = Contracts::deploy_optns(t0, terms.clone()).unwrap();
+            contract = Contracts::progress_optns(contract.schedule[0], contract)
+                .unwrap()
+                .1;
+            assert_eq!(contract.states.exercise_amount, Real::from(200));
+            let result = Contracts::progress_optns(contract.schedule[1], contract).unwrap();
+            assert_eq!(result.0, Real::from(200));
+
+            // A put out of the money is worthless.
+            let mut put = terms.clone();
+            put.option_type = Some(OptionType::P);
+            let mut contract = Contracts::deploy_optns(t0, put).unwrap();
+            contract = Contracts::progress_optns(contract.schedule[0], contract)
+                .unwrap()
+                .1;
+            assert_eq!(contract.states.exercise_amount, Real::from(0));
+
+            // European options can't be exercised before maturity.
+            let early =
+                ContractEvent::new(Time::from_values(2015, 02, 01, 00, 00, 00), EventType::XD);
+            let contract = Contracts::deploy_optns(t0, terms.clone()).unwrap();
+            assert!(Contracts::progress_optns(early, contract).is_err());
+
+            // Bermudan options only at the dates of the optionality cycle.
+            let mut bermudan = terms.clone();
+            bermudan.option_execution_type = Some(OptionExecutionType::B);
+            bermudan.cycle_of_optionality = Some(Cycle::Months(1, true));
+            let contract = Contracts::deploy_optns(t0, bermudan).unwrap();
+            assert!(Contracts::progress_optns(early, contract.clone()).is_ok());
+            let off_cycle =
+                ContractEvent::new(Time::from_values(2015, 02, 02, 00, 00, 00), EventType::XD);
+            assert!(Contracts::progress_optns(off_cycle, contract).is_err());
+
+            // American options at any time, they are then settled after the settlement days
+            // instead of at maturity.
+            terms.option_execution_type = Some(OptionExecutionType::A);
+            let mut contract = Contracts::deploy_optns(t0, terms).unwrap();
+            contract = Contracts::progress_optns(early, contract).unwrap().1;
+            assert!(Contracts::progress_optns(early, contract.clone()).is_err());
+            assert_eq!(contract.states.exercise_amount, Real::from(200));
+            let settlement = Time::from_values(2015, 02, 03, 00, 00, 00);
+            assert_eq!(
+                contract.schedule,
+                vec![ContractEvent::new(settlement, EventType::STD)]
+            );
+            assert_eq!(contract.states.time_at_maturity_date, settlement);
+            assert_ok!(<oracle::Module<Test>>::set(
+                contract.terms.market_object_code.unwrap(),
+                Real::from(90)
+            ));
+            let result = Contracts::progress_optns(contract.schedule[0], contract).unwrap();
+            assert_eq!(result.0, Real::from(200));
+        });
+    }
+
+    #[test]
+    fn exercise_by_holder_schedules_the_settlement() {
+        new_test_ext().execute_with(|| {
+            <timestamp::Module<Test>>::set_timestamp(1_420_070_400);
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.settlement_currency = Some(1);
+            terms.market_object_code = Some(H256::random());
+            terms.quantity = Real::from(10);
+            terms.contract_type = Some(ContractType::OPTNS);
+            terms.maturity_date = Time::from_values(2015, 04, 01, 00, 00, 00);
+            terms.option_execution_type = Some(OptionExecutionType::A);
+            terms.option_type = Some(OptionType::C);
+            terms.option_strike_1 = Real::from(100);
+            terms.settlement_days = Some(Period::Days(2));
+            terms.contract_structure = vec![Some(ContractStructure {
+                _object: terms.market_object_code.unwrap(),
+                _type: ContractReferenceType::MarketObjectIdentifier,
+                _role: ContractReferenceRole::Underlying,
+            })];
+            assert_ok!(<oracle::Module<Test>>::set(
+                terms.market_object_code.unwrap(),
+                Real::from(120)
+            ));
+            assert_ok!(<assets::Module<Test>>::mint(
+                terms.counterparty_id.unwrap(),
+                terms.currency.unwrap(),
+                Real::from(1000),
+            ));
+
+            let contract = Contracts::deploy_optns(t0, terms.clone()).unwrap();
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+            Contracts::scheduler_push(ScheduledEvent {
+                time: contract.schedule[0].time,
+                contract_id: id,
+                index: 0,
+            });
+
+            // The holder exercises the option, the Scheduler settles it after the settlement
+            // days instead of waiting for the exercise at maturity.
+            let event =
+                ContractEvent::new(Time::from_values(2015, 02, 01, 00, 00, 00), EventType::XD);
+            assert_ok!(Contracts::progress(event, id));
+            let settlement = Time::from_values(2015, 02, 03, 00, 00, 00);
+            assert_eq!(
+                Contracts::pending_event(id),
+                Some(ScheduledEvent {
+                    time: settlement,
+                    contract_id: id,
+                    index: 0,
+                })
+            );
+
+            assert_ok!(Contracts::scheduler_run(settlement));
+            assert_eq!(Contracts::pending_event(id), None);
+            assert_eq!(
+                <assets::Module<Test>>::balances((1, terms.creator_id.unwrap())),
+                Real::from(200)
+            );
+        });
+    }
+}
@@ -223,14 +223,14 @@ impl<T: Trait> Module<T> {
         }
 
         // Creating the schedule for all the events.
-        let mut schedule: Vec<Event> = Vec::new();
+        let mut schedule: Vec<ContractEvent> = Vec::new();
 
         // Inital exchange date event
-        let event = Event::new(terms.initial_exchange_date, EventType::IED);
+        let event = ContractEvent::new(terms.initial_exchange_date, EventType::IED);
         schedule.push(event);
 
         // Maturity date event
-        let event = Event::new(terms.maturity_date, EventType::MD);
+        let event = ContractEvent::new(terms.maturity_date, EventType::MD);
         schedule.push(event);
 
         // Principal prepayment event
@@ -259,7 +259,7 @@ impl<T: Trait> Module<T> {
             )?;
 
             for t in vec {
-                let event = Event::new(t, EventType::PP);
+                let event = ContractEvent::new(t, EventType::PP);
                 schedule.push(event);
             }
         }
@@ -269,7 +269,7 @@ impl<T: Trait> Module<T> {
         } else {
             for e in schedule.clone() {
                 if e.event_type == EventType::PP {
-                    let event = Event::new(e.time, EventType::PY);
+                    let event = ContractEvent::new(e.time, EventType::PY);
                     schedule.push(event);
                 }
             }
@@ -299,17 +299,17 @@ impl<T: Trait> Module<T> {
             )?;
 
             for t in vec {
-                let event = Event::new(t, EventType::FP);
+                let event = ContractEvent::new(t, EventType::FP);
                 schedule.push(event);
             }
         }
 
         // Purchase date event
-        let event = Event::new(terms.purchase_date, EventType::PRD);
+        let event = ContractEvent::new(terms.purchase_date, EventType::PRD);
         schedule.push(event);
 
         // Termination date event
-        let event = Event::new(terms.termination_date, EventType::TD);
+        let event = ContractEvent::new(terms.termination_date, EventType::TD);
         schedule.push(event);
 
         // Interest payment event
@@ -340,7 +340,7 @@ impl<T: Trait> Module<T> {
             )?;
 
             for t in vec {
-                let event = Event::new(t, EventType::IP);
+                let event = ContractEvent::new(t, EventType::IP);
                 schedule.push(event);
             }
         }
@@ -371,7 +371,7 @@ impl<T: Trait> Module<T> {
             )?;
 
             for t in vec {
-                let event = Event::new(t, EventType::IPCI);
+                let event = ContractEvent::new(t, EventType::IPCI);
                 schedule.push(event);
             }
         }
@@ -408,13 +408,13 @@ impl<T: Trait> Module<T> {
                 }
                 for t in vec {
                     if t != t_rry {
-                        let event = Event::new(t, EventType::RR);
+                        let event = ContractEvent::new(t, EventType::RR);
                         schedule.push(event);
                     }
                 }
             } else {
                 for t in vec {
-                    let event = Event::new(t, EventType::RR);
+                    let event = ContractEvent::new(t, EventType::RR);
                     schedule.push(event);
                 }
             }
@@ -444,7 +444,7 @@ impl<T: Trait> Module<T> {
 
             for t in vec {
                 if t > terms.status_date {
-                    let event = Event::new(t, EventType::RRF);
+                    let event = ContractEvent::new(t, EventType::RRF);
                     schedule.push(event);
                     break;
                 }
@@ -477,7 +477,7 @@ impl<T: Trait> Module<T> {
             )?;
 
             for t in vec {
-                let event = Event::new(t, EventType::SC);
+                let event = ContractEvent::new(t, EventType::SC);
                 schedule.push(event);
             }
         }
@@ -669,9 +669,15 @@ mod tests {
         type OnTimestampSet = ();
         type MinimumPeriod = MinimumPeriod;
     }
-    impl oracle::Trait for Test {}
-    impl assets::Trait for Test {}
-    impl Trait for Test {}
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
     type Contracts = Module<Test>;
 
     // This function basically just builds a genesis storage key/value store according to
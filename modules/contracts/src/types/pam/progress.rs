@@ -15,7 +15,10 @@ use super::*;
 
 // TODO: Add support for user-initiated events.
 impl<T: Trait> Module<T> {
-    pub fn progress_pam(event: Event, mut contract: Contract) -> ContractResult<(Real, Contract)> {
+    pub fn progress_pam(
+        event: ContractEvent,
+        mut contract: Contract,
+    ) -> ContractResult<(Real, Contract)> {
         // Getting t0 from the status_date attribute since they are equal.
         // (And status_date is not supposed to change)
         let t0 = contract.terms.status_date;
@@ -134,9 +137,15 @@ mod tests {
         type OnTimestampSet = ();
         type MinimumPeriod = MinimumPeriod;
     }
-    impl oracle::Trait for Test {}
-    impl assets::Trait for Test {}
-    impl Trait for Test {}
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
     type Contracts = Module<Test>;
 
     // This function basically just builds a genesis contract key/value store according to
@@ -174,7 +183,7 @@ mod tests {
 
             assert_eq!(
                 contract.schedule[0],
-                Event::new(Time::from_values(2015, 01, 02, 00, 00, 00), EventType::IED)
+                ContractEvent::new(Time::from_values(2015, 01, 02, 00, 00, 00), EventType::IED)
             );
             contract = Contracts::progress_pam(contract.schedule[0], contract)
                 .unwrap()
@@ -188,7 +197,7 @@ mod tests {
             // on the contract).
             assert_eq!(
                 contract.schedule[3],
-                Event::new(Time::from_values(2015, 04, 02, 00, 00, 00), EventType::MD)
+                ContractEvent::new(Time::from_values(2015, 04, 02, 00, 00, 00), EventType::MD)
             );
             contract = Contracts::progress_pam(contract.schedule[3], contract)
                 .unwrap()
@@ -0,0 +1,168 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    pub fn progress_stk(
+        event: ContractEvent,
+        mut contract: Contract,
+    ) -> ContractResult<(Real, Contract)> {
+        // Getting t0 from the status_date attribute since they are equal.
+        // (And status_date is not supposed to change)
+        let t0 = contract.terms.status_date;
+
+        match event.event_type {
+            EventType::PRD => Ok((
+                functions::pof_prd_stk(event, &contract),
+                functions::stf_prd_stk(event, &t0, contract),
+            )),
+            EventType::TD => Ok((
+                functions::pof_td_stk(event, &contract),
+                functions::stf_td_stk(event, &t0, contract),
+            )),
+            EventType::DV => Ok((
+                functions::pof_dv_stk(event, &contract),
+                functions::stf_dv_stk(event, &t0, contract),
+            )),
+            _ => Err("Event not applicable"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn progress_stk_works() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::STK);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.purchase_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+            terms.price_at_purchase_date = Real::from(20);
+            terms.quantity = Real::from(10);
+            terms.cycle_anchor_date_of_dividend = Time::from_values(2015, 03, 01, 00, 00, 00);
+            terms.cycle_of_dividend = Some(Period::Months(3));
+            terms.next_dividend_payment_amount = Real::from(3) / Real::from(2);
+            terms.termination_date = Time::from_values(2015, 04, 01, 00, 00, 00);
+            terms.price_at_termination_date = Real::from(25);
+
+            let mut contract = Contracts::deploy_stk(t0, terms).unwrap();
+
+            // The prices and the dividend are given per unit.
+            let result = Contracts::progress_stk(contract.schedule[0], contract).unwrap();
+            assert_eq!(result.0, Real::from(-200));
+            contract = result.1;
+
+            assert_eq!(
+                contract.schedule[1],
+                ContractEvent::new(Time::from_values(2015, 03, 01, 00, 00, 00), EventType::DV)
+            );
+            let result = Contracts::progress_stk(contract.schedule[1], contract).unwrap();
+            assert_eq!(result.0, Real::from(15));
+            contract = result.1;
+
+            assert_eq!(
+                contract.schedule[2],
+                ContractEvent::new(Time::from_values(2015, 04, 01, 00, 00, 00), EventType::TD)
+            );
+            let result = Contracts::progress_stk(contract.schedule[2], contract).unwrap();
+            assert_eq!(result.0, Real::from(250));
+            assert_eq!(
+                result.1.states.time_at_maturity_date,
+                Time::from_values(2015, 04, 01, 00, 00, 00)
+            );
+        });
+    }
+}
@@ -0,0 +1,386 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    pub fn deploy_swaps(t0: Time, input: Terms) -> ContractResult<Contract> {
+        // The ContractID, necessary to create any contract.
+        let mut terms = Terms::new(input.contract_id);
+
+        // Setting the Status Date to t0, since we don't want terms to change.
+        terms.status_date = t0;
+
+        // Mandatory in all cases -> NN
+        if input.contract_type.is_none()
+            || input.currency.is_none()
+            || input.delivery_settlement.is_none()
+        {
+            return Err("Error while initializing terms. [0]");
+        } else {
+            terms.contract_type = input.contract_type;
+            terms.currency = input.currency;
+            terms.delivery_settlement = input.delivery_settlement;
+        }
+
+        // Mandatory on stand-alone and parent contracts only and
+        // not applicable on child contracts -> NN(_,_,1)
+        // Note: The contract role of a swap is either RFL (receive fixed leg) or PFL (pay fixed leg).
+        if input.contract_deal_date.0.is_none()
+            || (input.contract_role != Some(ContractRole::RFL)
+                && input.contract_role != Some(ContractRole::PFL))
+            || input.creator_id.is_none()
+        {
+            return Err("Error while initializing terms. [1]");
+        } else {
+            terms.contract_deal_date = input.contract_deal_date;
+            terms.contract_role = input.contract_role;
+            terms.creator_id = input.creator_id;
+        }
+
+        // Mandatory on stand-alone and parent contracts only and
+        // optional on child contracts -> NN(_,_,2)
+        if input.counterparty_id.is_none() {
+            return Err("Error while initializing terms. [2]");
+        } else {
+            terms.counterparty_id = input.counterparty_id;
+        }
+
+        // Optional in all cases -> x
+        terms.market_object_code = input.market_object_code;
+        terms.market_value_observed = input.market_value_observed;
+        terms.settlement_currency = input.settlement_currency;
+
+        // Optional on stand-alone and parent contracts only and
+        // not applicable on child contracts -> x(_,_,1)
+        terms.contract_performance = input.contract_performance;
+        terms.non_performing_date = input.non_performing_date;
+        terms.seniority = input.seniority;
+
+        // Contract structure
+        // The swap must reference exactly one first leg and one second leg, both being existing
+        // contracts that don't belong to another parent contract.
+        let mut first_leg: Option<Contract> = None;
+        let mut second_leg: Option<Contract> = None;
+        for structure in input.contract_structure.iter().flatten() {
+            if (structure._type != ContractReferenceType::Contract
+                && structure._type != ContractReferenceType::ContractIdentifier)
+                || !<Self as Store>::Contracts::exists(structure._object)
+                || <Self as Store>::ParentContracts::exists(structure._object)
+            {
+                return Err("Error while initializing terms. [3]");
+            }
+            let leg = <Self as Store>::Contracts::get(structure._object);
+            match structure._role {
+                ContractReferenceRole::FirstLeg if first_leg.is_none() => first_leg = Some(leg),
+                ContractReferenceRole::SecondLeg if second_leg.is_none() => second_leg = Some(leg),
+                _ => return Err("Error while initializing terms. [3]"),
+            }
+        }
+        let (first_leg, second_leg) = match (first_leg, second_leg) {
+            (Some(first_leg), Some(second_leg)) => (first_leg, second_leg),
+            _ => return Err("Error while initializing terms. [3]"),
+        };
+        terms.contract_structure = input.contract_structure;
+
+        // The legs must have the roles implied by the role of the swap. When receiving the fixed
+        // leg, the first leg is an asset and the second leg a liability, and vice versa.
+        let (first_role, second_role) = if terms.contract_role == Some(ContractRole::RFL) {
+            (Some(ContractRole::RPA), Some(ContractRole::RPL))
+        } else {
+            (Some(ContractRole::RPL), Some(ContractRole::RPA))
+        };
+        if first_leg.terms.contract_role != first_role
+            || second_leg.terms.contract_role != second_role
+        {
+            return Err("Error while initializing terms. [4]");
+        }
+
+        // The legs must be contracts between the same parties as the swap, otherwise anyone could
+        // take over the contracts of other users.
+        for leg in [&first_leg, &second_leg].iter() {
+            if leg.terms.creator_id != terms.creator_id
+                || leg.terms.counterparty_id != terms.counterparty_id
+            {
+                return Err("Error while initializing terms. [4]");
+            }
+        }
+
+        // Group 5
+        // Business rule ‘a’ applies unconditionally
+        terms.purchase_date = input.purchase_date; // -> x(5,0,1)
+
+        // Business rule ‘a’ applies if at least one of the unconditional CAs of this group is defined
+        if input.purchase_date.0.is_some() {
+            if input.price_at_purchase_date.0.is_none() {
+                return Err("Error while initializing terms. [5]");
+            } else {
+                terms.price_at_purchase_date = input.price_at_purchase_date;
+                // -> NN(5,1,1)
+            }
+        }
+
+        // Group 6
+        // Business rule ‘a’ applies unconditionally
+        terms.termination_date = input.termination_date; // -> x(6,0,1)
+
+        // Business rule ‘a’ applies if at least one of the unconditional CAs of this group is defined
+        if input.termination_date.0.is_some() {
+            if input.price_at_termination_date.0.is_none() {
+                return Err("Error while initializing terms. [6]");
+            } else {
+                terms.price_at_termination_date = input.price_at_termination_date;
+                // -> NN(6,1,1)
+            }
+        }
+
+        // Checking if the terms all have allowed values
+        if terms.is_valid() == false {
+            return Err("Error while initializing terms. [7]");
+        }
+
+        // Creating the schedule for all the events.
+        let mut schedule: Vec<ContractEvent> = Vec::new();
+
+        // Purchase date event
+        let event = ContractEvent::new(terms.purchase_date, EventType::PRD);
+        schedule.push(event);
+
+        // Termination date event
+        let event = ContractEvent::new(terms.termination_date, EventType::TD);
+        schedule.push(event);
+
+        // Leg events
+        // Note: The events that both legs have in common are only added once, since a single event
+        // of the swap progresses both legs.
+        for e in first_leg.schedule.iter().chain(second_leg.schedule.iter()) {
+            let event = ContractEvent::new(e.time, e.event_type);
+            if e.time >= t0 && !schedule.contains(&event) {
+                schedule.push(event);
+            }
+        }
+
+        // Remove any events with Time == None or after the termination date.
+        // Note: The unusual control flow is because we want to use the swap_remove method,
+        // which has O(1) complexity but requires a more complex solution to work.
+        let mut i = 0;
+        while i < schedule.len() {
+            if schedule[i].time == Time(None)
+                || (terms.termination_date != Time(None)
+                    && schedule[i].time > terms.termination_date)
+            {
+                schedule.swap_remove(i);
+            } else {
+                i += 1;
+            }
+        }
+
+        // Ordering the schedule
+        schedule.sort_unstable();
+
+        // Initializing the contract states
+        let mut states = States::new();
+
+        // Time At Maturity Date variable
+        // Note: The swap matures with its last leg, unless one of the legs has no maturity.
+        if first_leg.states.time_at_maturity_date == Time(None)
+            || second_leg.states.time_at_maturity_date == Time(None)
+        {
+            states.time_at_maturity_date = Time(None);
+        } else if first_leg.states.time_at_maturity_date > second_leg.states.time_at_maturity_date {
+            states.time_at_maturity_date = first_leg.states.time_at_maturity_date;
+        } else {
+            states.time_at_maturity_date = second_leg.states.time_at_maturity_date;
+        }
+
+        // Contract Performance variable
+        states.contract_performance = terms.contract_performance;
+
+        // Status Date variable
+        states.status_date = t0;
+
+        // Returning the initialized Contract State
+        Ok(Contract {
+            terms: terms,
+            states: states,
+            schedule: schedule,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn deploy_swaps_works() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let creator_id = H256::random();
+            let counterparty_id = H256::random();
+            let mut legs = Vec::new();
+            for (role, rate) in vec![(ContractRole::RPA, 10), (ContractRole::RPL, 4)] {
+                let id = H256::random();
+                let mut terms = Terms::new(id);
+                terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+                terms.contract_id = id;
+                terms.contract_role = Some(role);
+                terms.contract_type = Some(ContractType::PAM);
+                terms.counterparty_id = Some(counterparty_id);
+                terms.creator_id = Some(creator_id);
+                terms.currency = Some(1);
+                terms.day_count_convention = Some(DayCountConvention::_30E360);
+                terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+                terms.maturity_date = Time::from_values(2015, 07, 02, 00, 00, 00);
+                terms.nominal_interest_rate = Real::from(rate) / Real::from(100);
+                terms.notional_principal = Real::from(1000);
+                terms.premium_discount_at_ied = Real::from(0);
+                terms.cycle_of_interest_payment = Some(Cycle::Months(3, true));
+                terms.scaling_effect = None;
+                let contract = Contracts::deploy_pam(t0, terms).unwrap();
+                <Contracts as Store>::Contracts::insert(id, contract);
+                legs.push(id);
+            }
+
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RFL);
+            terms.contract_type = Some(ContractType::SWAPS);
+            terms.counterparty_id = Some(counterparty_id);
+            terms.creator_id = Some(creator_id);
+            terms.currency = Some(1);
+            terms.settlement_currency = Some(1);
+            terms.delivery_settlement = Some(DeliverySettlement::S);
+            terms.contract_structure = vec![
+                Some(ContractStructure {
+                    _object: legs[0],
+                    _type: ContractReferenceType::Contract,
+                    _role: ContractReferenceRole::FirstLeg,
+                }),
+                Some(ContractStructure {
+                    _object: legs[1],
+                    _type: ContractReferenceType::Contract,
+                    _role: ContractReferenceRole::SecondLeg,
+                }),
+            ];
+
+            // The roles of the legs must match the role of the swap.
+            terms.contract_role = Some(ContractRole::PFL);
+            assert!(Contracts::deploy_swaps(t0, terms.clone()).is_err());
+            terms.contract_role = Some(ContractRole::RFL);
+
+            // The legs must be contracts between the parties of the swap.
+            let mut foreign = terms.clone();
+            foreign.creator_id = Some(H256::random());
+            assert!(Contracts::deploy_swaps(t0, foreign).is_err());
+            let mut foreign = terms.clone();
+            foreign.counterparty_id = Some(H256::random());
+            assert!(Contracts::deploy_swaps(t0, foreign).is_err());
+
+            // Both legs are required.
+            let mut incomplete = terms.clone();
+            incomplete.contract_structure.pop();
+            assert!(Contracts::deploy_swaps(t0, incomplete).is_err());
+
+            // The events of the legs are merged into a single schedule.
+            let contract = Contracts::deploy_swaps(t0, terms).unwrap();
+            assert_eq!(
+                contract.schedule,
+                vec![
+                    ContractEvent::new(Time::from_values(2015, 01, 02, 00, 00, 00), EventType::IED),
+                    ContractEvent::new(Time::from_values(2015, 04, 02, 00, 00, 00), EventType::IP),
+                    ContractEvent::new(Time::from_values(2015, 07, 02, 00, 00, 00), EventType::IP),
+                    ContractEvent::new(Time::from_values(2015, 07, 02, 00, 00, 00), EventType::MD),
+                ]
+            );
+            assert_eq!(
+                contract.states.time_at_maturity_date,
+                Time::from_values(2015, 07, 02, 00, 00, 00)
+            );
+        });
+    }
+}
@@ -0,0 +1,414 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    /// Progresses a swap and its legs. It returns the payoffs of the first and of the second leg
+    /// separately, so that they can either be netted or settled gross, and the progressed legs.
+    /// Nothing is stored, the legs are stored together with the swap once the event succeeded.
+    pub fn progress_swaps(
+        event: ContractEvent,
+        mut contract: Contract,
+    ) -> ContractResult<(Real, Real, Contract, Vec<(H256, Contract)>)> {
+        match event.event_type {
+            EventType::PRD => {
+                let payoff = utilities::contract_role_sign(contract.terms.contract_role)
+                    * Real::from(-1)
+                    * contract.terms.price_at_purchase_date;
+                contract.states.status_date = event.time;
+                Ok((payoff, Real::from(0), contract, Vec::new()))
+            }
+            EventType::TD => {
+                let payoff = utilities::contract_role_sign(contract.terms.contract_role)
+                    * contract.terms.price_at_termination_date;
+                contract.states.time_at_maturity_date = event.time;
+                contract.states.status_date = event.time;
+                Ok((payoff, Real::from(0), contract, Vec::new()))
+            }
+            _ => {
+                let mut payoffs = (Real::from(0), Real::from(0));
+                let mut legs: Vec<(H256, Contract)> = Vec::new();
+                for structure in contract.terms.contract_structure.iter().flatten() {
+                    let leg = <Self as Store>::Contracts::get(structure._object);
+
+                    // Only the legs that have this event in their schedule are progressed.
+                    let leg_event = match leg
+                        .schedule
+                        .iter()
+                        .find(|e| e.time == event.time && e.event_type == event.event_type)
+                    {
+                        Some(e) => *e,
+                        None => continue,
+                    };
+                    let result = Self::progress_payoff(leg_event, leg)?;
+                    legs.push((structure._object, result.1));
+
+                    if structure._role == ContractReferenceRole::FirstLeg {
+                        payoffs.0 = result.0;
+                    } else {
+                        payoffs.1 = result.0;
+                    }
+                }
+                contract.states.status_date = event.time;
+                Ok((payoffs.0, payoffs.1, contract, legs))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn progress_swaps_works() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let creator_id = H256::random();
+            let counterparty_id = H256::random();
+            let mut legs = Vec::new();
+            for (role, rate) in vec![(ContractRole::RPA, 10), (ContractRole::RPL, 4)] {
+                let id = H256::random();
+                let mut terms = Terms::new(id);
+                terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+                terms.contract_id = id;
+                terms.contract_role = Some(role);
+                terms.contract_type = Some(ContractType::PAM);
+                terms.counterparty_id = Some(counterparty_id);
+                terms.creator_id = Some(creator_id);
+                terms.currency = Some(1);
+                terms.day_count_convention = Some(DayCountConvention::_30E360);
+                terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+                terms.maturity_date = Time::from_values(2015, 07, 02, 00, 00, 00);
+                terms.nominal_interest_rate = Real::from(rate) / Real::from(100);
+                terms.notional_principal = Real::from(1000);
+                terms.premium_discount_at_ied = Real::from(0);
+                terms.cycle_of_interest_payment = Some(Cycle::Months(3, true));
+                terms.scaling_effect = None;
+                let contract = Contracts::deploy_pam(t0, terms).unwrap();
+                <Contracts as Store>::Contracts::insert(id, contract);
+                legs.push(id);
+            }
+
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RFL);
+            terms.contract_type = Some(ContractType::SWAPS);
+            terms.counterparty_id = Some(counterparty_id);
+            terms.creator_id = Some(creator_id);
+            terms.currency = Some(1);
+            terms.settlement_currency = Some(1);
+            terms.delivery_settlement = Some(DeliverySettlement::S);
+            terms.contract_structure = vec![
+                Some(ContractStructure {
+                    _object: legs[0],
+                    _type: ContractReferenceType::Contract,
+                    _role: ContractReferenceRole::FirstLeg,
+                }),
+                Some(ContractStructure {
+                    _object: legs[1],
+                    _type: ContractReferenceType::Contract,
+                    _role: ContractReferenceRole::SecondLeg,
+                }),
+            ];
+            terms.delivery_settlement = Some(DeliverySettlement::S);
+            let contract = Contracts::deploy_swaps(t0, terms).unwrap();
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+            <Contracts as Store>::ParentContracts::insert(legs[0], id);
+            <Contracts as Store>::ParentContracts::insert(legs[1], id);
+
+            // The legs can't be progressed on their own.
+            assert!(Contracts::progress(contract.schedule[0], legs[0]).is_err());
+
+            // The principal of both legs is exchanged at the initial exchange date.
+            let result = Contracts::progress_swaps(contract.schedule[0], contract).unwrap();
+            assert_eq!(result.0, Real::from(-1000));
+            assert_eq!(result.1, Real::from(1000));
+            let contract = result.2;
+            assert_eq!(result.3.len(), 2);
+            assert_eq!(result.3[0].0, legs[0]);
+            assert_eq!(result.3[0].1.states.notional_principal, Real::from(1000));
+            for (id, leg) in result.3 {
+                <Contracts as Store>::Contracts::insert(id, leg);
+            }
+
+            // The fixed leg pays 25 and the floating leg 10 over the first quarter.
+            let result = Contracts::progress_swaps(contract.schedule[1], contract).unwrap();
+            assert_eq!(result.0, Real::from(25));
+            assert_eq!(result.1, Real::from(-10));
+            assert_eq!(result.2.states.status_date, result.2.schedule[1].time);
+        });
+    }
+
+    #[test]
+    fn progress_settles_swaps() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let creator_id = H256::random();
+            let counterparty_id = H256::random();
+            let mut legs = Vec::new();
+            for (role, rate) in vec![(ContractRole::RPA, 10), (ContractRole::RPL, 4)] {
+                let id = H256::random();
+                let mut terms = Terms::new(id);
+                terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+                terms.contract_id = id;
+                terms.contract_role = Some(role);
+                terms.contract_type = Some(ContractType::PAM);
+                terms.counterparty_id = Some(counterparty_id);
+                terms.creator_id = Some(creator_id);
+                terms.currency = Some(1);
+                terms.day_count_convention = Some(DayCountConvention::_30E360);
+                terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+                terms.maturity_date = Time::from_values(2015, 07, 02, 00, 00, 00);
+                terms.nominal_interest_rate = Real::from(rate) / Real::from(100);
+                terms.notional_principal = Real::from(1000);
+                terms.premium_discount_at_ied = Real::from(0);
+                terms.cycle_of_interest_payment = Some(Cycle::Months(3, true));
+                terms.scaling_effect = None;
+                let contract = Contracts::deploy_pam(t0, terms).unwrap();
+                <Contracts as Store>::Contracts::insert(id, contract);
+                legs.push(id);
+            }
+
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RFL);
+            terms.contract_type = Some(ContractType::SWAPS);
+            terms.counterparty_id = Some(counterparty_id);
+            terms.creator_id = Some(creator_id);
+            terms.currency = Some(1);
+            terms.settlement_currency = Some(1);
+            terms.delivery_settlement = Some(DeliverySettlement::S);
+            terms.contract_structure = vec![
+                Some(ContractStructure {
+                    _object: legs[0],
+                    _type: ContractReferenceType::Contract,
+                    _role: ContractReferenceRole::FirstLeg,
+                }),
+                Some(ContractStructure {
+                    _object: legs[1],
+                    _type: ContractReferenceType::Contract,
+                    _role: ContractReferenceRole::SecondLeg,
+                }),
+            ];
+            assert_ok!(<assets::Module<Test>>::mint(
+                creator_id,
+                1,
+                Real::from(1000)
+            ));
+            assert_ok!(<assets::Module<Test>>::mint(
+                counterparty_id,
+                1,
+                Real::from(1000)
+            ));
+
+            // Net settlement only transfers the difference between the legs.
+            let mut net = terms.clone();
+            net.contract_id = H256::random();
+            let contract = Contracts::deploy_swaps(t0, net.clone()).unwrap();
+            <Contracts as Store>::Contracts::insert(net.contract_id, contract.clone());
+            assert_ok!(Contracts::progress(contract.schedule[0], net.contract_id));
+            assert_ok!(Contracts::progress(contract.schedule[1], net.contract_id));
+            assert_eq!(
+                <assets::Module<Test>>::balances((1, creator_id)),
+                Real::from(1015)
+            );
+            assert_eq!(
+                <assets::Module<Test>>::balances((1, counterparty_id)),
+                Real::from(985)
+            );
+
+            // Gross settlement transfers the payoff of each leg, so the swap becomes
+            // non-performing if the creator can't pay the first leg, and neither leg is settled.
+            terms.delivery_settlement = Some(DeliverySettlement::D);
+            assert_ok!(<assets::Module<Test>>::burn(
+                creator_id,
+                1,
+                Real::from(1015)
+            ));
+            let contract = Contracts::deploy_swaps(t0, terms.clone()).unwrap();
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+            assert!(Contracts::progress(contract.schedule[2], id).is_ok());
+            assert!(Contracts::progress(contract.schedule[0], id).is_ok());
+            assert_eq!(
+                <Contracts as Store>::Contracts::get(id)
+                    .states
+                    .contract_performance,
+                Some(ContractPerformance::DL)
+            );
+            assert_eq!(
+                <assets::Module<Test>>::balances((1, counterparty_id)),
+                Real::from(985)
+            );
+            assert_eq!(<Contracts as Store>::Arrears::get(id).len(), 1);
+        });
+    }
+
+    #[test]
+    fn progress_swaps_stores_no_leg_if_one_fails() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let creator_id = H256::random();
+            let counterparty_id = H256::random();
+            let mut legs = Vec::new();
+            for (role, oracle_value) in vec![(ContractRole::RPA, true), (ContractRole::RPL, false)]
+            {
+                let id = H256::random();
+                let mut terms = Terms::new(id);
+                terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+                terms.contract_id = id;
+                terms.contract_role = Some(role);
+                terms.contract_type = Some(ContractType::PAM);
+                terms.counterparty_id = Some(counterparty_id);
+                terms.creator_id = Some(creator_id);
+                terms.currency = Some(1);
+                terms.day_count_convention = Some(DayCountConvention::_30E360);
+                terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+                terms.maturity_date = Time::from_values(2015, 07, 02, 00, 00, 00);
+                terms.nominal_interest_rate = Real::from(4) / Real::from(100);
+                terms.notional_principal = Real::from(1000);
+                terms.premium_discount_at_ied = Real::from(0);
+                terms.cycle_of_rate_reset = Some(Cycle::Months(3, true));
+                terms.market_object_code_rate_reset = Some(H256::random());
+                terms.rate_spread = Real::from(0);
+                terms.scaling_effect = None;
+                // Only the oracle of the first leg has a value.
+                if oracle_value {
+                    assert_ok!(<oracle::Module<Test>>::set(
+                        terms.market_object_code_rate_reset.unwrap(),
+                        Real::from(5) / Real::from(100)
+                    ));
+                }
+                let contract = Contracts::deploy_pam(t0, terms).unwrap();
+                <Contracts as Store>::Contracts::insert(id, contract);
+                legs.push(id);
+            }
+
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RFL);
+            terms.contract_type = Some(ContractType::SWAPS);
+            terms.counterparty_id = Some(counterparty_id);
+            terms.creator_id = Some(creator_id);
+            terms.currency = Some(1);
+            terms.settlement_currency = Some(1);
+            terms.delivery_settlement = Some(DeliverySettlement::S);
+            terms.contract_structure = vec![
+                Some(ContractStructure {
+                    _object: legs[0],
+                    _type: ContractReferenceType::Contract,
+                    _role: ContractReferenceRole::FirstLeg,
+                }),
+                Some(ContractStructure {
+                    _object: legs[1],
+                    _type: ContractReferenceType::Contract,
+                    _role: ContractReferenceRole::SecondLeg,
+                }),
+            ];
+            let contract = Contracts::deploy_swaps(t0, terms).unwrap();
+            <Contracts as Store>::Contracts::insert(id, contract.clone());
+            <Contracts as Store>::ParentContracts::insert(legs[0], id);
+            <Contracts as Store>::ParentContracts::insert(legs[1], id);
+
+            // The rate reset of the second leg fails, so the first leg isn't progressed either.
+            let first_leg = <Contracts as Store>::Contracts::get(legs[0]);
+            let event = *contract
+                .schedule
+                .iter()
+                .find(|e| e.event_type == EventType::RR)
+                .unwrap();
+            assert!(Contracts::progress(event, id).is_err());
+            assert_eq!(<Contracts as Store>::Contracts::get(legs[0]), first_leg);
+            assert_eq!(<Contracts as Store>::Contracts::get(id), contract);
+        });
+    }
+}
@@ -0,0 +1,428 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    pub fn deploy_swppv(t0: Time, input: Terms) -> ContractResult<Contract> {
+        // The ContractID, necessary to create any contract.
+        let mut terms = Terms::new(input.contract_id);
+
+        // Setting the Status Date to t0, since we don't want terms to change.
+        terms.status_date = t0;
+
+        // Mandatory in all cases -> NN
+        if input.contract_type.is_none()
+            || input.currency.is_none()
+            || input.day_count_convention.is_none()
+            || input.delivery_settlement.is_none()
+            || input.initial_exchange_date.0.is_none()
+            || input.maturity_date.0.is_none()
+            || input.nominal_interest_rate.0.is_none()
+            || input.nominal_interest_rate_2.0.is_none()
+            || input.notional_principal.0.is_none()
+        {
+            return Err("Error while initializing terms. [0]");
+        } else {
+            terms.contract_type = input.contract_type;
+            terms.currency = input.currency;
+            terms.day_count_convention = input.day_count_convention;
+            terms.delivery_settlement = input.delivery_settlement;
+            terms.initial_exchange_date = input.initial_exchange_date;
+            terms.maturity_date = input.maturity_date;
+            terms.nominal_interest_rate = input.nominal_interest_rate;
+            terms.nominal_interest_rate_2 = input.nominal_interest_rate_2;
+            terms.notional_principal = input.notional_principal;
+        }
+
+        // Mandatory on stand-alone and parent contracts only and
+        // not applicable on child contracts -> NN(_,_,1)
+        // Note: The contract role of a swap is either RFL (receive fixed leg) or PFL (pay fixed leg).
+        if input.contract_deal_date.0.is_none()
+            || (input.contract_role != Some(ContractRole::RFL)
+                && input.contract_role != Some(ContractRole::PFL))
+            || input.creator_id.is_none()
+        {
+            return Err("Error while initializing terms. [1]");
+        } else {
+            terms.contract_deal_date = input.contract_deal_date;
+            terms.contract_role = input.contract_role;
+            terms.creator_id = input.creator_id;
+        }
+
+        // Mandatory on stand-alone and parent contracts only and
+        // optional on child contracts -> NN(_,_,2)
+        if input.counterparty_id.is_none() {
+            return Err("Error while initializing terms. [2]");
+        } else {
+            terms.counterparty_id = input.counterparty_id;
+        }
+
+        // Optional in all cases -> x
+        terms.business_day_convention = input.business_day_convention;
+        terms.calendar = input.calendar;
+        terms.end_of_month_convention = input.end_of_month_convention;
+        terms.market_object_code = input.market_object_code;
+        terms.market_value_observed = input.market_value_observed;
+        terms.settlement_currency = input.settlement_currency;
+
+        // Optional on stand-alone and parent contracts only and
+        // not applicable on child contracts -> x(_,_,1)
+        terms.contract_performance = input.contract_performance;
+        terms.non_performing_date = input.non_performing_date;
+        terms.seniority = input.seniority;
+
+        // Group 2
+        // At least one of the CAs of this group has to be defined, both legs pay interest
+        if input.cycle_anchor_date_of_interest_payment.0.is_none()
+            && input.cycle_of_interest_payment.is_none()
+        {
+            return Err("Error while initializing terms. [3]");
+        } else {
+            terms.cycle_anchor_date_of_interest_payment =
+                input.cycle_anchor_date_of_interest_payment; // -> x(2,0,_)
+            terms.cycle_of_interest_payment = input.cycle_of_interest_payment; // -> x(2,0,_)
+        }
+
+        // Group 5
+        // Business rule ‘a’ applies unconditionally
+        terms.purchase_date = input.purchase_date; // -> x(5,0,1)
+
+        // Business rule ‘a’ applies if at least one of the unconditional CAs of this group is defined
+        if input.purchase_date.0.is_some() {
+            if input.price_at_purchase_date.0.is_none() {
+                return Err("Error while initializing terms. [4]");
+            } else {
+                terms.price_at_purchase_date = input.price_at_purchase_date;
+                // -> NN(5,1,1)
+            }
+        }
+
+        // Group 6
+        // Business rule ‘a’ applies unconditionally
+        terms.termination_date = input.termination_date; // -> x(6,0,1)
+
+        // Business rule ‘a’ applies if at least one of the unconditional CAs of this group is defined
+        if input.termination_date.0.is_some() {
+            if input.price_at_termination_date.0.is_none() {
+                return Err("Error while initializing terms. [5]");
+            } else {
+                terms.price_at_termination_date = input.price_at_termination_date;
+                // -> NN(6,1,1)
+            }
+        }
+
+        // Group 9
+        // The floating leg is reset from the oracle, so the rate reset terms are mandatory
+        if (input.cycle_anchor_date_of_rate_reset.0.is_none()
+            && input.cycle_of_rate_reset.is_none())
+            || input.market_object_code_rate_reset.is_none()
+        {
+            return Err("Error while initializing terms. [6]");
+        } else {
+            terms.cycle_anchor_date_of_rate_reset = input.cycle_anchor_date_of_rate_reset; // -> x(9,0,_)
+            terms.cycle_of_rate_reset = input.cycle_of_rate_reset; // -> x(9,0,_)
+            terms.market_object_code_rate_reset = input.market_object_code_rate_reset; // -> NN(9,1,_)
+            terms.fixing_days = input.fixing_days; // -> x(9,1,_)
+            terms.rate_multiplier = input.rate_multiplier; // -> x(9,1,_)
+            terms.rate_spread = input.rate_spread; // -> x(9,1,_)
+        }
+
+        // Checking if the terms all have allowed values
+        if terms.is_valid() == false {
+            return Err("Error while initializing terms. [7]");
+        }
+
+        // Creating the schedule for all the events.
+        let mut schedule: Vec<ContractEvent> = Vec::new();
+
+        // Inital exchange date event
+        let event = ContractEvent::new(terms.initial_exchange_date, EventType::IED);
+        schedule.push(event);
+
+        // Maturity date event
+        let event = ContractEvent::new(terms.maturity_date, EventType::MD);
+        schedule.push(event);
+
+        // Purchase date event
+        let event = ContractEvent::new(terms.purchase_date, EventType::PRD);
+        schedule.push(event);
+
+        // Termination date event
+        let event = ContractEvent::new(terms.termination_date, EventType::TD);
+        schedule.push(event);
+
+        // Interest payment events
+        // Note: With net settlement both legs are paid in a single event, otherwise each leg has
+        // its own event.
+        let mut s: Time = Time(None);
+        if terms.cycle_anchor_date_of_interest_payment == Time(None) {
+            s = utilities::sum_cycle(
+                terms.initial_exchange_date,
+                terms.cycle_of_interest_payment,
+                terms.end_of_month_convention,
+            );
+        } else {
+            s = terms.cycle_anchor_date_of_interest_payment;
+        }
+
+        let vec = utilities::schedule(
+            s,
+            terms.maturity_date,
+            terms.cycle_of_interest_payment,
+            terms.end_of_month_convention,
+        )?;
+
+        for t in vec {
+            if terms.delivery_settlement == Some(DeliverySettlement::S) {
+                let event = ContractEvent::new(t, EventType::IP);
+                schedule.push(event);
+            } else {
+                let event = ContractEvent::new(t, EventType::IPFX);
+                schedule.push(event);
+                let event = ContractEvent::new(t, EventType::IPFL);
+                schedule.push(event);
+            }
+        }
+
+        // Rate reset variable event
+        let mut s: Time = Time(None);
+        if terms.cycle_anchor_date_of_rate_reset == Time(None) {
+            s = utilities::sum_cycle(
+                terms.initial_exchange_date,
+                terms.cycle_of_rate_reset,
+                terms.end_of_month_convention,
+            );
+        } else {
+            s = terms.cycle_anchor_date_of_rate_reset;
+        }
+
+        let vec = utilities::schedule(
+            s,
+            terms.maturity_date,
+            terms.cycle_of_rate_reset,
+            terms.end_of_month_convention,
+        )?;
+
+        // Note: A rate reset at the maturity date would have no effect.
+        for t in vec {
+            if t < terms.maturity_date {
+                let event = ContractEvent::new(t, EventType::RR);
+                schedule.push(event);
+            }
+        }
+
+        // Remove any events with Time == None
+        // Note: The unusual control flow is because we want to use the swap_remove method,
+        // which has O(1) complexity but requires a more complex solution to work.
+        let mut i = 0;
+        while i < schedule.len() {
+            if schedule[i].time == Time(None) {
+                schedule.swap_remove(i);
+            } else {
+                i += 1;
+            }
+        }
+
+        // Ordering the schedule
+        schedule.sort_unstable();
+
+        // Initializing the contract states
+        let mut states = States::new();
+
+        // Time At Maturity Date variable
+        states.time_at_maturity_date = terms.maturity_date;
+
+        // Notional Principal variable
+        if terms.initial_exchange_date > t0 {
+            states.notional_principal = Real::from(0);
+        } else {
+            states.notional_principal =
+                utilities::contract_role_sign(terms.contract_role) * terms.notional_principal;
+        }
+
+        // Nominal Interest Rate variables (fixed and floating legs)
+        states.nominal_interest_rate = terms.nominal_interest_rate;
+        if terms.initial_exchange_date > t0 {
+            states.nominal_interest_rate_2 = Real::from(0);
+        } else {
+            states.nominal_interest_rate_2 = terms.nominal_interest_rate_2;
+        }
+
+        // Accrued Interest variables (fixed and floating legs)
+        states.accrued_interest = Real::from(0);
+        states.accrued_interest_2 = Real::from(0);
+
+        // Contract Performance variable
+        states.contract_performance = terms.contract_performance;
+
+        // Status Date variable
+        states.status_date = t0;
+
+        // Returning the initialized Contract State
+        Ok(Contract {
+            terms: terms,
+            states: states,
+            schedule: schedule,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn deploy_swppv_works() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RFL);
+            terms.contract_type = Some(ContractType::SWPPV);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.day_count_convention = Some(DayCountConvention::_30E360);
+            terms.initial_exchange_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+            terms.maturity_date = Time::from_values(2015, 07, 02, 00, 00, 00);
+            terms.nominal_interest_rate = Real::from(12) / Real::from(100);
+            terms.nominal_interest_rate_2 = Real::from(6) / Real::from(100);
+            terms.notional_principal = Real::from(1000);
+            terms.cycle_of_interest_payment = Some(Cycle::Months(3, true));
+            terms.cycle_of_rate_reset = Some(Cycle::Months(3, true));
+            terms.market_object_code_rate_reset = Some(H256::random());
+
+            // Swaps either receive or pay the fixed leg.
+            terms.contract_role = Some(ContractRole::RPA);
+            assert!(Contracts::deploy_swppv(t0, terms.clone()).is_err());
+            terms.contract_role = Some(ContractRole::RFL);
+
+            // Net settlement pays both legs in a single event.
+            terms.delivery_settlement = Some(DeliverySettlement::S);
+            let contract = Contracts::deploy_swppv(t0, terms.clone()).unwrap();
+            assert_eq!(
+                contract.schedule,
+                vec![
+                    ContractEvent::new(Time::from_values(2015, 01, 02, 00, 00, 00), EventType::IED),
+                    ContractEvent::new(Time::from_values(2015, 04, 02, 00, 00, 00), EventType::IP),
+                    ContractEvent::new(Time::from_values(2015, 04, 02, 00, 00, 00), EventType::RR),
+                    ContractEvent::new(Time::from_values(2015, 07, 02, 00, 00, 00), EventType::IP),
+                    ContractEvent::new(Time::from_values(2015, 07, 02, 00, 00, 00), EventType::MD),
+                ]
+            );
+
+            // Gross settlement pays each leg separately.
+            terms.delivery_settlement = Some(DeliverySettlement::D);
+            let contract = Contracts::deploy_swppv(t0, terms).unwrap();
+            assert_eq!(
+                contract.schedule,
+                vec![
+                    ContractEvent::new(Time::from_values(2015, 01, 02, 00, 00, 00), EventType::IED),
+                    ContractEvent::new(
+                        Time::from_values(2015, 04, 02, 00, 00, 00),
+                        EventType::IPFX
+                    ),
+                    ContractEvent::new(
+                        Time::from_values(2015, 04, 02, 00, 00, 00),
+                        EventType::IPFL
+                    ),
+                    ContractEvent::new(Time::from_values(2015, 04, 02, 00, 00, 00), EventType::RR),
+                    ContractEvent::new(
+                        Time::from_values(2015, 07, 02, 00, 00, 00),
+                        EventType::IPFX
+                    ),
+                    ContractEvent::new(
+                        Time::from_values(2015, 07, 02, 00, 00, 00),
+                        EventType::IPFL
+                    ),
+                    ContractEvent::new(Time::from_values(2015, 07, 02, 00, 00, 00), EventType::MD),
+                ]
+            );
+        });
+    }
+}
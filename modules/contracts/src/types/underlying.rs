@@ -0,0 +1,200 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    /// Returns the value at the given time of an underlying referenced in the contract structure
+    /// of a contract. The underlying can be a market object (read from the oracle) or a deployed
+    /// cash, stock or commodity position.
+    pub fn underlying_value(structure: ContractStructure, time: Time) -> ContractResult<Real> {
+        if structure._role != ContractReferenceRole::Underlying {
+            return Err("Contract structure is not an underlying");
+        }
+
+        match structure._type {
+            ContractReferenceType::MarketObjectIdentifier => {
+                Ok(<oracle::Module<T>>::value_at(structure._object, time))
+            }
+            ContractReferenceType::Contract | ContractReferenceType::ContractIdentifier => {
+                if !<Self as Store>::Contracts::exists(structure._object) {
+                    return Err("Referenced contract doesn't exist");
+                }
+                Self::position_value(&<Self as Store>::Contracts::get(structure._object), time)
+            }
+            _ => Err("Underlying reference type not supported"),
+        }
+    }
+
+    /// Returns the value at the given time of a cash, stock or commodity position.
+    pub fn position_value(contract: &Contract, time: Time) -> ContractResult<Real> {
+        match contract.terms.contract_type {
+            Some(ContractType::CSH) => Ok(contract.states.notional_principal),
+            Some(ContractType::STK) | Some(ContractType::COM) => {
+                // The positions are valued at the market price of one unit.
+                if contract.terms.market_object_code.is_none() {
+                    return Err("Underlying has no market object code");
+                }
+                Ok(utilities::contract_role_sign(contract.terms.contract_role)
+                    * contract.terms.quantity
+                    * <oracle::Module<T>>::value_at(
+                        contract.terms.market_object_code.unwrap(), // This unwrap will never panic.
+                        time,
+                    ))
+            }
+            _ => Err("Underlying contract type not supported"),
+        }
+    }
+
+    /// Returns the contract referenced as the underlying in the contract structure of a contract.
+    /// It must only be used on contracts whose deployment checked that the underlying exists.
+    pub fn underlying_contract(contract: &Contract) -> Contract {
+        let mut underlying = Contract::default();
+        for structure in contract.terms.contract_structure.iter().flatten() {
+            if structure._role == ContractReferenceRole::Underlying {
+                underlying = <Self as Store>::Contracts::get(structure._object);
+            }
+        }
+        underlying
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl oracle::Trait for Test {
+        type Event = ();
+    }
+    impl assets::Trait for Test {
+        type Event = ();
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Contracts = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn underlying_value_works() {
+        new_test_ext().execute_with(|| {
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            let id = H256::random();
+            let mut terms = Terms::new(id);
+            terms.contract_deal_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+            terms.contract_id = id;
+            terms.contract_role = Some(ContractRole::RPA);
+            terms.contract_type = Some(ContractType::STK);
+            terms.counterparty_id = Some(H256::random());
+            terms.creator_id = Some(H256::random());
+            terms.currency = Some(1);
+            terms.purchase_date = Time::from_values(2015, 01, 02, 00, 00, 00);
+            terms.price_at_purchase_date = Real::from(20);
+            terms.quantity = Real::from(10);
+            let market_object_code = H256::random();
+            terms.market_object_code = Some(market_object_code);
+            assert_ok!(<oracle::Module<Test>>::set(
+                market_object_code,
+                Real::from(30)
+            ));
+
+            let mut structure = ContractStructure {
+                _object: id,
+                _type: ContractReferenceType::Contract,
+                _role: ContractReferenceRole::Underlying,
+            };
+
+            // The underlying has to be deployed.
+            assert!(Contracts::underlying_value(structure, t0).is_err());
+
+            let contract = Contracts::deploy_stk(t0, terms).unwrap();
+            <Contracts as Store>::Contracts::insert(id, contract);
+            assert_eq!(
+                Contracts::underlying_value(structure, t0),
+                Ok(Real::from(300))
+            );
+
+            // Market objects are read directly from the oracle.
+            structure._object = market_object_code;
+            structure._type = ContractReferenceType::MarketObjectIdentifier;
+            assert_eq!(
+                Contracts::underlying_value(structure, t0),
+                Ok(Real::from(30))
+            );
+
+            // Only underlyings have a value.
+            structure._role = ContractReferenceRole::FirstLeg;
+            assert!(Contracts::underlying_value(structure, t0).is_err());
+        });
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+/// This is NOT an ACTUS utility function. Given the anchor dates of an array schedule, it returns
+/// the index of the array entry that is in force at time *t*, i.e. the index of the latest anchor
+/// date that is not after *t*. If *t* is before the first anchor date, it returns None.
+pub fn array_index(arr_s: &Vec<Time>, t: Time) -> Option<usize> {
+    let mut index = None;
+    for i in 0..arr_s.len() {
+        if arr_s[i] <= t {
+            index = Some(i);
+        } else {
+            break;
+        }
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn array_index_works() {
+        let s1 = Time::from_values(2019, 06, 01, 12, 00, 00);
+        let s2 = Time::from_values(2019, 07, 01, 12, 00, 00);
+        let s3 = Time::from_values(2019, 08, 01, 12, 00, 00);
+        let arr_s = vec![s1, s2, s3];
+        // Testing a time before the first anchor date.
+        let t = Time::from_values(2019, 05, 01, 12, 00, 00);
+        assert_eq!(array_index(&arr_s, t), None);
+        // Testing times on and between anchor dates.
+        assert_eq!(array_index(&arr_s, s1), Some(0));
+        let t = Time::from_values(2019, 06, 15, 12, 00, 00);
+        assert_eq!(array_index(&arr_s, t), Some(0));
+        assert_eq!(array_index(&arr_s, s2), Some(1));
+        // Testing a time after the last anchor date.
+        let t = Time::from_values(2020, 01, 01, 12, 00, 00);
+        assert_eq!(array_index(&arr_s, t), Some(2));
+        // Testing an empty array.
+        assert_eq!(array_index(&Vec::new(), t), None);
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+/// This is NOT an ACTUS utility function. It tells if the performance of a contract is covered by
+/// a credit enhancement, that is if it is at least as bad as the credit event type covered (from
+/// better to worse: delayed, delinquent and default).
+pub fn credit_event_covered(
+    contract_performance: Option<ContractPerformance>,
+    credit_event_type_covered: Option<CreditEventTypeCovered>,
+) -> bool {
+    let severity = match contract_performance {
+        Some(ContractPerformance::DL) => 1,
+        Some(ContractPerformance::DQ) => 2,
+        Some(ContractPerformance::DF) => 3,
+        _ => return false,
+    };
+    match credit_event_type_covered {
+        Some(CreditEventTypeCovered::DL) => severity >= 1,
+        Some(CreditEventTypeCovered::DQ) => severity >= 2,
+        Some(CreditEventTypeCovered::DF) => severity >= 3,
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn credit_event_covered_works() {
+        let dq = Some(CreditEventTypeCovered::DQ);
+        assert!(!credit_event_covered(None, dq));
+        assert!(!credit_event_covered(Some(ContractPerformance::PF), dq));
+        assert!(!credit_event_covered(Some(ContractPerformance::DL), dq));
+        assert!(credit_event_covered(Some(ContractPerformance::DQ), dq));
+        assert!(credit_event_covered(Some(ContractPerformance::DF), dq));
+        assert!(!credit_event_covered(Some(ContractPerformance::DF), None));
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+/// This is NOT an ACTUS utility function. It returns the penalty accrued by a delayed or delinquent
+/// contract from its last event (or from its non-performing date, if later) until time *t*, at the
+/// delinquency rate on the notional principal. The penalty is paid to the lender.
+pub fn delinquency_penalty(contract: &Contract, t: Time) -> Real {
+    if (contract.states.contract_performance != Some(ContractPerformance::DL)
+        && contract.states.contract_performance != Some(ContractPerformance::DQ))
+        || contract.terms.delinquency_rate == Real(None)
+        || contract.terms.day_count_convention.is_none()
+        || contract.states.notional_principal == Real(None)
+    {
+        return Real::from(0);
+    }
+
+    let mut from = contract.states.status_date;
+    if contract.states.non_performing_date > from {
+        from = contract.states.non_performing_date;
+    }
+
+    contract_role_sign(contract.terms.contract_role)
+        * contract.terms.delinquency_rate
+        * year_fraction(
+            from,
+            t,
+            contract.terms.day_count_convention.unwrap(), // This unwrap will never panic.
+        )
+        * contract.states.notional_principal.abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+
+    #[test]
+    fn delinquency_penalty_works() {
+        let mut contract = Contract::default();
+        contract.terms = Terms::new(H256::random());
+        contract.terms.contract_role = Some(ContractRole::RPA);
+        contract.terms.day_count_convention = Some(DayCountConvention::_30E360);
+        contract.terms.delinquency_rate = Real::from(10) / Real::from(100);
+        contract.states.contract_performance = Some(ContractPerformance::PF);
+        contract.states.notional_principal = Real::from(1000);
+        contract.states.status_date = Time::from_values(2015, 01, 01, 00, 00, 00);
+        let t = Time::from_values(2015, 07, 01, 00, 00, 00);
+
+        // Performant contracts have no penalty.
+        assert_eq!(delinquency_penalty(&contract, t), Real::from(0));
+
+        // The penalty accrues from the non-performing date.
+        contract.states.contract_performance = Some(ContractPerformance::DL);
+        contract.states.non_performing_date = Time::from_values(2015, 04, 01, 00, 00, 00);
+        assert_eq!(delinquency_penalty(&contract, t), Real::from(25));
+
+        // Lenders receive it and borrowers pay it.
+        contract.terms.contract_role = Some(ContractRole::RPL);
+        assert_eq!(delinquency_penalty(&contract, t), Real::from(-25));
+    }
+}
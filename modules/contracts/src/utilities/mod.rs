@@ -14,23 +14,37 @@
 use super::*;
 
 mod annuity_amount;
+mod array_index;
 mod array_schedule;
 mod business_day;
 mod business_day_shift;
 mod contract_default;
 mod contract_role_sign;
+mod credit_event_covered;
+mod delinquency_penalty;
 mod end_of_month_shift;
+mod open_schedule;
+mod performance_transition;
+mod prepayment_amount;
 mod schedule;
 mod sum_cycle;
+mod sum_period;
 mod year_fraction;
 
 pub use annuity_amount::*;
+pub use array_index::*;
 pub use array_schedule::*;
 pub use business_day::*;
 pub use business_day_shift::*;
 pub use contract_default::*;
 pub use contract_role_sign::*;
+pub use credit_event_covered::*;
+pub use delinquency_penalty::*;
 pub use end_of_month_shift::*;
+pub use open_schedule::*;
+pub use performance_transition::*;
+pub use prepayment_amount::*;
 pub use schedule::*;
 pub use sum_cycle::*;
+pub use sum_period::*;
 pub use year_fraction::*;
@@ -0,0 +1,101 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+/// This is NOT an ACTUS utility function. It returns the times of the schedule with anchor *s* and
+/// cycle *c* (and no end date) that fall in the interval (*from*, *to*]. It is used to create the
+/// schedule of contracts without a maturity date piece by piece.
+pub fn open_schedule(
+    s: Time,
+    from: Time,
+    to: Time,
+    cycle: Option<Cycle>,
+    end_of_month_convention: Option<EndOfMonthConvention>,
+) -> ContractResult<Vec<Time>> {
+    let mut vec: Vec<Time> = Vec::new();
+
+    // Checking the cases where the schedule has at most one element.
+    if s == Time(None) || s > to {
+        return Ok(vec);
+    }
+    if cycle == None || s == to {
+        if s > from {
+            vec.push(s);
+        }
+        return Ok(vec);
+    }
+
+    // The times after the end of the interval are dropped, so the schedule is always created with
+    // a short stub.
+    let cycle = match cycle.unwrap() {
+        Cycle::Days(int, _) => Cycle::Days(int, true),
+        Cycle::Months(int, _) => Cycle::Months(int, true),
+        Cycle::Years(int, _) => Cycle::Years(int, true),
+    };
+
+    for t in schedule(s, to, Some(cycle), end_of_month_convention)? {
+        if t > from && t <= to {
+            vec.push(t);
+        }
+    }
+
+    Ok(vec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_schedule_works() {
+        let s = Time::from_values(2019, 01, 31, 12, 00, 00);
+        let from = Time::from_values(2019, 03, 01, 12, 00, 00);
+        let to = Time::from_values(2019, 06, 30, 12, 00, 00);
+
+        // Testing s==None and s>to.
+        let vec: Vec<Time> = Vec::new();
+        assert_eq!(
+            open_schedule(Time(None), from, to, None, None),
+            Ok(vec.clone())
+        );
+        assert_eq!(open_schedule(to, s, from, None, None), Ok(vec.clone()));
+
+        // Testing cycle==None.
+        assert_eq!(open_schedule(s, from, to, None, None), Ok(vec.clone()));
+        assert_eq!(open_schedule(from, s, to, None, None), Ok(vec![from]));
+
+        // Testing Cycle::Months, the stub is ignored.
+        let s = Time::from_values(2019, 01, 15, 12, 00, 00);
+        let vec = vec![
+            Time::from_values(2019, 03, 15, 12, 00, 00),
+            Time::from_values(2019, 04, 15, 12, 00, 00),
+            Time::from_values(2019, 05, 15, 12, 00, 00),
+            Time::from_values(2019, 06, 15, 12, 00, 00),
+        ];
+        let c = Some(Cycle::Months(1, false));
+        assert_eq!(open_schedule(s, from, to, c, None), Ok(vec.clone()));
+        let c = Some(Cycle::Months(1, true));
+        assert_eq!(open_schedule(s, from, to, c, None), Ok(vec));
+
+        // Testing Cycle::Days, the end of the interval is included.
+        let s = Time::from_values(2019, 01, 31, 12, 00, 00);
+        let from = Time::from_values(2019, 05, 30, 12, 00, 00);
+        let vec = vec![
+            Time::from_values(2019, 05, 31, 12, 00, 00),
+            Time::from_values(2019, 06, 30, 12, 00, 00),
+        ];
+        let c = Some(Cycle::Days(30, false));
+        assert_eq!(open_schedule(s, from, to, c, None), Ok(vec));
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+/// This is NOT an ACTUS utility function. It returns the performance of a non-performing contract
+/// at time *t*. A delayed contract becomes delinquent once the grace period since the
+/// non-performing date is over, and defaults once the delinquency period is over as well. Without a
+/// grace period the contract doesn't become delinquent, and without a delinquency period it doesn't
+/// default. Performant and defaulted contracts don't change.
+pub fn performance_transition(
+    contract_performance: Option<ContractPerformance>,
+    non_performing_date: Time,
+    t: Time,
+    grace_period: Option<Period>,
+    delinquency_period: Option<Period>,
+) -> Option<ContractPerformance> {
+    match contract_performance {
+        Some(ContractPerformance::DL) | Some(ContractPerformance::DQ) => {
+            if non_performing_date == Time(None) {
+                contract_performance
+            } else if delinquency_period.is_some()
+                && t > sum_period(non_performing_date, delinquency_period, None)
+            {
+                Some(ContractPerformance::DF)
+            } else if grace_period.is_some()
+                && t > sum_period(non_performing_date, grace_period, None)
+            {
+                Some(ContractPerformance::DQ)
+            } else {
+                contract_performance
+            }
+        }
+        _ => contract_performance,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn performance_transition_works() {
+        let npd = Time::from_values(2015, 01, 01, 00, 00, 00);
+        let grace = Some(Period::Days(5));
+        let delinquency = Some(Period::Months(1));
+        let dl = Some(ContractPerformance::DL);
+        let dq = Some(ContractPerformance::DQ);
+        let df = Some(ContractPerformance::DF);
+        let pf = Some(ContractPerformance::PF);
+
+        // Performant contracts don't change.
+        let t = Time::from_values(2016, 01, 01, 00, 00, 00);
+        assert_eq!(performance_transition(pf, npd, t, grace, delinquency), pf);
+
+        // Within the grace period.
+        let t = Time::from_values(2015, 01, 06, 00, 00, 00);
+        assert_eq!(performance_transition(dl, npd, t, grace, delinquency), dl);
+
+        // Within the delinquency period.
+        let t = Time::from_values(2015, 01, 07, 00, 00, 00);
+        assert_eq!(performance_transition(dl, npd, t, grace, delinquency), dq);
+        assert_eq!(performance_transition(dq, npd, t, grace, delinquency), dq);
+
+        // After the delinquency period.
+        let t = Time::from_values(2015, 02, 02, 00, 00, 00);
+        assert_eq!(performance_transition(dl, npd, t, grace, delinquency), df);
+        assert_eq!(performance_transition(df, npd, t, grace, delinquency), df);
+
+        // A missing period only skips the escalation it governs: without a grace period the
+        // contract still defaults after the delinquency period, without a delinquency period it
+        // only becomes delinquent, and without either it isn't escalated.
+        assert_eq!(performance_transition(dl, npd, t, None, delinquency), df);
+        assert_eq!(performance_transition(dl, npd, t, grace, None), dq);
+        assert_eq!(performance_transition(dl, npd, t, None, None), dl);
+    }
+}
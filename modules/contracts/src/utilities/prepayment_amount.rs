@@ -0,0 +1,46 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+/// This is NOT an ACTUS utility function. This function calculates the principal prepaid by a PP
+/// event: the amount of the event, capped at the outstanding notional principal. Scheduled PP
+/// events (which have no amount) don't prepay anything.
+pub fn prepayment_amount(event: ContractEvent, contract: &Contract) -> Real {
+    if event.amount == Real(None) || contract.states.notional_principal == Real(None) {
+        return Real::from(0);
+    }
+    Real::min(event.amount.abs(), contract.states.notional_principal.abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prepayment_amount_works() {
+        let t = Time::from_values(2015, 02, 01, 00, 00, 00);
+        let mut contract = Contract::default();
+        contract.states.notional_principal = Real::from(-1000);
+
+        // Scheduled events don't prepay anything.
+        let event = ContractEvent::new(t, EventType::PP);
+        assert_eq!(prepayment_amount(event, &contract), Real::from(0));
+
+        // Partial and full prepayments.
+        let event = ContractEvent::with_amount(t, EventType::PP, Real::from(400));
+        assert_eq!(prepayment_amount(event, &contract), Real::from(400));
+        let event = ContractEvent::with_amount(t, EventType::PP, Real::from(1500));
+        assert_eq!(prepayment_amount(event, &contract), Real::from(1000));
+    }
+}
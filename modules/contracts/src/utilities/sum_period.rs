@@ -0,0 +1,53 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+/// This is NOT an ACTUS utility function. This function calculates the addition of a time and a
+/// period (e.g. the settlement days), a missing period being taken as zero days.
+pub fn sum_period(
+    t: Time,
+    p: Option<Period>,
+    end_of_month_convention: Option<EndOfMonthConvention>,
+) -> Time {
+    let c = match p {
+        Some(Period::Days(int)) => Some(Cycle::Days(int, true)),
+        Some(Period::Months(int)) => Some(Cycle::Months(int, true)),
+        Some(Period::Years(int)) => Some(Cycle::Years(int, true)),
+        None => Some(Cycle::Days(0, true)),
+    };
+    sum_cycle(t, c, end_of_month_convention)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sum_period_works() {
+        let t = Time::from_values(2019, 06, 06, 12, 00, 00);
+        assert_eq!(
+            sum_period(Time(None), Some(Period::Days(2)), None),
+            Time(None)
+        );
+        assert_eq!(sum_period(t, None, None), t);
+        assert_eq!(
+            sum_period(t, Some(Period::Days(2)), None),
+            Time::from_values(2019, 06, 08, 12, 00, 00)
+        );
+        assert_eq!(
+            sum_period(t, Some(Period::Months(1)), None),
+            Time::from_values(2019, 07, 06, 12, 00, 00)
+        );
+    }
+}
@@ -18,6 +18,10 @@
 //! context is simply a structure that holds a value (implemented using Real) and a timestamp
 //! (implemented using Time). Each oracle is uniquely identified by a 256-bit integer
 //! (implemented using H256).
+//!
+//! The previous values of every oracle are kept for a limited period, so that contracts executing
+//! past events (for example after a halt of the chain) use the value that was valid at the time
+//! of the event.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 // The above line is needed to compile the Wasm binaries.
@@ -25,26 +29,34 @@
 // Importing crates necessary to work with Substrate.
 use codec::{Decode, Encode};
 use primitives::H256;
-use support::{decl_module, decl_storage, dispatch::Result};
+use support::{decl_event, decl_module, decl_storage, dispatch::Result};
 use system::ensure_root;
 // // This import is used to convert the timestamp to a Time.
 use sr_primitives::traits::SaturatedConversion;
 
 // Importing crates from Katal's runtime.
-use structures::{Real, Time};
+use structures::{Real, Time, Vec};
 
 // Importing the rest of the files in this crate.
 mod oracle_state;
 mod set;
+mod value_at;
 use oracle_state::*;
 
 // This module's configuration trait.
-pub trait Trait: system::Trait + timestamp::Trait {}
+pub trait Trait: system::Trait + timestamp::Trait {
+    type Event: From<Event> + Into<<Self as system::Trait>::Event>;
+}
 
 // This module's storage items.
 decl_storage! {
     trait Store for Module<T: Trait> as OracleStorage {
         pub Oracles get(fn oracles): map H256 => OracleState;
+        // The previous states of each oracle, keyed by their position in its history.
+        pub History get(fn history): map (H256, u32) => OracleState;
+        // The positions of the earliest state kept and of the next state in the history of each
+        // oracle.
+        pub HistoryRange get(fn history_range): map H256 => (u32, u32);
     }
 }
 
@@ -53,6 +65,9 @@ decl_module! {
     // The module declaration.
     pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 
+        // Default implementation of the function that deposits the events.
+        fn deposit_event() = default;
+
         // Set the value of an existing data feed or creating a new one.
         pub fn dispatch_set(origin, id: H256, value: Real) -> Result {
             // Only chain root should be able to set this value.
@@ -66,3 +81,11 @@ decl_module! {
         }
     }
 }
+
+// This module's events.
+decl_event!(
+    pub enum Event {
+        /// An oracle was set to a value, at the given time.
+        OracleUpdated(H256, Real, Time),
+    }
+);
@@ -13,6 +13,10 @@
 
 use super::*;
 
+/// The number of seconds for which the previous values of an oracle are kept. The latest value
+/// set before that period is kept as well, since it is still valid at the beginning of the period.
+pub const HISTORY_PERIOD: u64 = 366 * 86_400;
+
 // This function sets an (or creates a new) oracle.
 impl<T: Trait> Module<T> {
     pub fn set(id: H256, value: Real) -> Result {
@@ -28,6 +32,18 @@ impl<T: Trait> Module<T> {
         // Store input value in storage.
         <Self as Store>::Oracles::insert(id, state);
 
+        // Adding the state to the history, and removing the states older than the history period.
+        let (mut first, mut next) = <Self as Store>::HistoryRange::get(id);
+        <Self as Store>::History::insert((id, next), state);
+        next += 1;
+        let start = Time::from_unix(unix_time.saturating_sub(HISTORY_PERIOD));
+        while first + 1 < next && <Self as Store>::History::get((id, first + 1)).time <= start {
+            <Self as Store>::History::remove((id, first));
+            first += 1;
+        }
+        <Self as Store>::HistoryRange::insert(id, (first, next));
+        Self::deposit_event(Event::OracleUpdated(id, value, time));
+
         // Return Ok if successful.
         Ok(())
     }
@@ -89,7 +105,9 @@ mod tests {
         type OnTimestampSet = ();
         type MinimumPeriod = MinimumPeriod;
     }
-    impl Trait for Test {}
+    impl Trait for Test {
+        type Event = ();
+    }
     type Oracle = Module<Test>;
 
     // This function basically just builds a genesis storage key/value store according to
@@ -113,6 +131,30 @@ mod tests {
             // Get oracle state from storage.
             assert_eq!(time, <Oracle as Store>::Oracles::get(id).time);
             assert_eq!(value, <Oracle as Store>::Oracles::get(id).value);
+            assert_eq!(<Oracle as Store>::HistoryRange::get(id), (0, 1));
+            assert_eq!(<Oracle as Store>::History::get((id, 0)).value, value);
+        });
+    }
+
+    #[test]
+    fn set_prunes_the_history() {
+        new_test_ext().execute_with(|| {
+            let id = H256::zero();
+
+            // Setting the oracle every 200 days.
+            for i in 0..5 {
+                <timestamp::Module<Test>>::set_timestamp(1_420_070_400 + i * 200 * 86_400);
+                assert_ok!(Oracle::set(id, Real::from(i as i64)));
+            }
+
+            // The values set more than the history period ago are removed, except for the one
+            // that was still valid at the beginning of the period.
+            assert_eq!(<Oracle as Store>::HistoryRange::get(id), (1, 5));
+            assert!(!<Oracle as Store>::History::exists((id, 0)));
+            assert_eq!(
+                <Oracle as Store>::History::get((id, 1)).value,
+                Real::from(1)
+            );
         });
     }
 
@@ -0,0 +1,158 @@
+// Copyright 2020 by Trinkler Software AG (Switzerland).
+// This file is part of Katal Chain.
+//
+// Katal Chain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version <http://www.gnu.org/licenses/>.
+//
+// Katal Chain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+use super::*;
+
+// This function gets the value of an oracle at a given time.
+impl<T: Trait> Module<T> {
+    /// Returns the value that the oracle had at the given time, that is the latest value set at or
+    /// before that time. Oracles without a value at that time (e.g. before the first value, or
+    /// before the history period) return `Real(None)`.
+    pub fn value_at(id: H256, time: Time) -> Real {
+        // Oracles set before the history was kept only know their current value.
+        if !<Self as Store>::HistoryRange::exists(id) {
+            let state = <Self as Store>::Oracles::get(id);
+            if <Self as Store>::Oracles::exists(id) && state.time <= time {
+                return state.value;
+            }
+            return Real(None);
+        }
+
+        // The history is ordered by time, so it is searched for the first state after the time.
+        let (mut low, mut high) = <Self as Store>::HistoryRange::get(id);
+        let first = low;
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if <Self as Store>::History::get((id, middle)).time <= time {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        if low == first {
+            return Real(None);
+        }
+        <Self as Store>::History::get((id, low - 1)).value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::H256;
+    // The testing primitives are very useful for avoiding having to work with signatures
+    // or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
+    use sr_primitives::{
+        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup},
+        Perbill,
+    };
+    use support::{assert_ok, impl_outer_origin, parameter_types};
+
+    impl_outer_origin! {
+        pub enum Origin for Test {}
+    }
+
+    // For testing the module, we construct most of a mock runtime. This means
+    // first constructing a configuration type (`Test`) which `impl`s each of the
+    // configuration traits of modules we want to use.
+    #[derive(Clone, Eq, PartialEq)]
+    pub struct Test;
+    parameter_types! {
+        pub const BlockHashCount: u64 = 250;
+        pub const MaximumBlockWeight: u32 = 1024;
+        pub const MaximumBlockLength: u32 = 2 * 1024;
+        pub const AvailableBlockRatio: Perbill = Perbill::one();
+    }
+    impl system::Trait for Test {
+        type Origin = Origin;
+        type Index = u64;
+        type Call = ();
+        type BlockNumber = u64;
+        type Hash = H256;
+        type Hashing = BlakeTwo256;
+        type AccountId = u64;
+        type Lookup = IdentityLookup<Self::AccountId>;
+        type Header = Header;
+        type Event = ();
+        type BlockHashCount = BlockHashCount;
+        type MaximumBlockWeight = MaximumBlockWeight;
+        type AvailableBlockRatio = AvailableBlockRatio;
+        type MaximumBlockLength = MaximumBlockLength;
+        type Version = ();
+    }
+
+    pub const MILLISECS_PER_BLOCK: u64 = 6000;
+    pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
+    parameter_types! {
+        pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
+    }
+    impl timestamp::Trait for Test {
+        type Moment = u64;
+        type OnTimestampSet = ();
+        type MinimumPeriod = MinimumPeriod;
+    }
+    impl Trait for Test {
+        type Event = ();
+    }
+    type Oracle = Module<Test>;
+
+    // This function basically just builds a genesis storage key/value store according to
+    // our desired mockup.
+    fn new_test_ext() -> runtime_io::TestExternalities {
+        system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn value_at_works() {
+        new_test_ext().execute_with(|| {
+            let id = H256::zero();
+
+            // Setting the oracle at several times.
+            <timestamp::Module<Test>>::set_timestamp(1_420_070_400);
+            assert_ok!(Oracle::set(id, Real::from(1)));
+            <timestamp::Module<Test>>::set_timestamp(1_420_070_400 + 2 * 86_400);
+            assert_ok!(Oracle::set(id, Real::from(2)));
+            <timestamp::Module<Test>>::set_timestamp(1_420_070_400 + 4 * 86_400);
+            assert_ok!(Oracle::set(id, Real::from(3)));
+
+            // Getting the value valid at each time.
+            let t0 = Time::from_values(2015, 01, 01, 00, 00, 00);
+            assert_eq!(Oracle::value_at(id, t0), Real::from(1));
+            assert_eq!(Oracle::value_at(id, t0.add_days(1)), Real::from(1));
+            assert_eq!(Oracle::value_at(id, t0.add_days(2)), Real::from(2));
+            assert_eq!(Oracle::value_at(id, t0.add_days(3)), Real::from(2));
+            assert_eq!(Oracle::value_at(id, t0.add_days(10)), Real::from(3));
+
+            // Times before the first value have no value.
+            let t1 = Time::from_values(2014, 01, 01, 00, 00, 00);
+            assert_eq!(Oracle::value_at(id, t1), Real(None));
+            assert_eq!(Oracle::value_at(H256::random(), t0), Real(None));
+
+            // Oracles set without history only have their current value.
+            let id = H256::random();
+            <Oracle as Store>::Oracles::insert(
+                id,
+                OracleState {
+                    time: t0.add_days(2),
+                    value: Real::from(4),
+                },
+            );
+            assert_eq!(Oracle::value_at(id, t0.add_days(1)), Real(None));
+            assert_eq!(Oracle::value_at(id, t0.add_days(2)), Real::from(4));
+        });
+    }
+}
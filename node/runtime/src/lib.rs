@@ -108,8 +108,8 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     // implementation changes and behavior does not, then leave spec_version as
     // is and increment impl_version.
     // Spec version is also needed for transaction signing.
-    spec_version: 2,
-    impl_version: 3,
+    spec_version: 4,
+    impl_version: 4,
     apis: RUNTIME_API_VERSIONS,
 };
 
@@ -245,12 +245,16 @@ impl sudo::Trait for Runtime {
 }
 
 impl contracts::Trait for Runtime {
-    // Add `type Event = Event;` in case of event usage
+    type Event = Event;
 }
 
-impl oracle::Trait for Runtime {}
+impl oracle::Trait for Runtime {
+    type Event = Event;
+}
 
-impl assets::Trait for Runtime {}
+impl assets::Trait for Runtime {
+    type Event = Event;
+}
 
 construct_runtime!(
 	pub enum Runtime where
@@ -268,9 +272,9 @@ construct_runtime!(
 		Sudo: sudo,
 		RandomnessCollectiveFlip: randomness_collective_flip::{Module, Call, Storage},
         // Custom modules
-        Oracle: oracle::{Module, Call, Storage},
-        Assets: assets::{Module, Call, Storage},
-        Contracts: contracts::{Module, Call, Storage},
+        Oracle: oracle::{Module, Call, Storage, Event},
+        Assets: assets::{Module, Call, Storage, Event},
+        Contracts: contracts::{Module, Call, Storage, Event<T>},
 	}
 );
 